@@ -4,11 +4,31 @@
 use std::{
     default::Default,
     fmt,
-    ops::{Add, Mul, Neg, Sub},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
 };
 
-/// A 2-vector of `f64`s.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// A 2-vector with coordinates of type `Coord`.
+///
+/// Vectors are ordered lexicographically, comparing `x` first and then
+/// `y`. This ordering has no geometric meaning; it exists so that
+/// vectors can be sorted deterministically and used as keys in ordered
+/// collections such as `BTreeMap`.
+///
+/// `Eq` is derived, so it's only implemented when `Coord: Eq`. In
+/// particular, `V2<f64>` is only `PartialEq`, and comparing float vectors
+/// with `==` is usually a mistake, since rounding error makes vectors
+/// that ought to be equal differ in the last few bits. Use
+/// [`approx_eq`](#method.approx_eq) for those instead.
+///
+/// With the `serde` feature enabled, vectors serialize as structs with
+/// fields `x` and `y`.
+///
+/// The layout is `#[repr(C)]`, so a `V2<T>` has the same layout as a
+/// `[T; 2]` holding `[x, y]`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct V2<Coord> {
     pub x: Coord,
     pub y: Coord,
@@ -28,43 +48,1422 @@ impl<T> V2<T> {
     pub fn new(x: T, y: T) -> Self {
         V2 { x, y, }
     }
+
+    /// Converts the vector into an array `[x, y]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(2., 3.);
+    /// assert_eq!([2., 3.], v.to_array());
+    /// ```
+    pub fn to_array(self) -> [T; 2] {
+        [self.x, self.y]
+    }
+
+    /// Views the vector as an array `[x, y]` without copying.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(2., 3.);
+    /// assert_eq!(&[2., 3.], v.as_array());
+    /// ```
+    pub fn as_array(&self) -> &[T; 2] {
+        // Safe because `V2<T>` is `#[repr(C)]` with two fields of type
+        // `T`, which has the same size, alignment, and field order as
+        // `[T; 2]`.
+        unsafe { &*(self as *const V2<T> as *const [T; 2]) }
+    }
+
+    /// Applies a function to both components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(2.5, -1.5);
+    /// assert_eq!(V2::new(2., -2.), v.map(f64::floor));
+    ///
+    /// let u = V2::new(3, 4);
+    /// assert_eq!(V2::new(3., 4.), u.map(|c| c as f64));
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, mut f: F) -> V2<U> {
+        V2::new(f(self.x), f(self.y))
+    }
+
+    /// Converts the coordinates to another type using `From`, which is
+    /// lossless. (For lossy conversions, use [`map`](#method.map) with
+    /// `as`.)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v: V2<i32> = V2::new(3, -4);
+    /// assert_eq!(V2::new(3., -4.), v.cast::<f64>());
+    /// ```
+    pub fn cast<U: From<T>>(self) -> V2<U> {
+        self.map(U::from)
+    }
+
+    /// Combines two vectors component-wise using a function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1., 5.);
+    /// let u = V2::new(3., 2.);
+    /// assert_eq!(V2::new(3., 5.), v.zip_with(u, f64::max));
+    /// assert_eq!(V2::new(3., 10.), v.zip_with(u, |a, b| a * b));
+    /// ```
+    pub fn zip_with<U, W, F>(self, other: V2<U>, mut f: F) -> V2<W>
+        where F: FnMut(T, U) -> W
+    {
+        V2::new(f(self.x, other.x), f(self.y, other.y))
+    }
+}
+
+impl<T> From<[T; 2]> for V2<T> {
+    /// Converts an array `[x, y]` into a vector.
+    fn from([x, y]: [T; 2]) -> Self {
+        V2::new(x, y)
+    }
+}
+
+#[test]
+fn array_conversion_test() {
+    let v = V2::from([1, 2]);
+    assert_eq!(1, v.x);
+    assert_eq!(2, v.y);
+    assert_eq!([1, 2], v.to_array());
+    assert_eq!(&[1, 2], v.as_array());
+    assert_eq!(v.x, v.as_array()[0]);
+    assert_eq!(v.y, v.as_array()[1]);
+
+    let u: V2<f64> = [3., 4.].into();
+    assert_eq!(V2::new(3., 4.), u);
+    assert!(std::ptr::eq(&u.x, &u.as_array()[0]));
+}
+
+#[test]
+fn map_test() {
+    let v = V2::new(1, -2);
+    assert_eq!(V2::new(2, -4), v.map(|c| 2 * c));
+    assert_eq!(V2::new("1".to_owned(), "-2".to_owned()), v.map(|c| c.to_string()));
+}
+
+#[test]
+fn cast_test() {
+    let v: V2<f32> = V2::new(0.5, -2.);
+    assert_eq!(V2::new(0.5f64, -2.), v.cast());
+
+    let u: V2<u8> = V2::new(200, 7);
+    assert_eq!(V2::new(200i32, 7), u.cast());
+}
+
+#[test]
+fn zip_with_test() {
+    let v = V2::new(1, 5);
+    let u = V2::new(3, 2);
+    assert_eq!(V2::new(3, 5), v.zip_with(u, std::cmp::max));
+    assert_eq!(V2::new(1, 2), v.zip_with(u, std::cmp::min));
+    assert_eq!(V2::new(3, 10), v.zip_with(u, |a, b| a * b));
+    assert_eq!(v.component_mul(&u), v.zip_with(u, |a, b| a * b));
+    assert_eq!(V2::new((1, 'a'), (5, 'b')), v.zip_with(V2::new('a', 'b'), |a, b| (a, b)));
+}
+
+impl<T: Copy> V2<T> {
+    /// Swaps the components, returning `(y, x)`. This transposes the
+    /// vector across the line `y = x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(2, 1), V2::new(1, 2).swap());
+    /// ```
+    pub fn swap(&self) -> V2<T> {
+        V2::new(self.y, self.x)
+    }
+
+    /// Returns a copy of the vector with `x` replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1., 2.);
+    /// assert_eq!(V2::new(5., 2.), v.with_x(5.));
+    /// ```
+    pub fn with_x(&self, x: T) -> V2<T> {
+        V2::new(x, self.y)
+    }
+
+    /// Returns a copy of the vector with `y` replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1., 2.);
+    /// assert_eq!(V2::new(1., 5.), v.with_y(5.));
+    /// ```
+    pub fn with_y(&self, y: T) -> V2<T> {
+        V2::new(self.x, y)
+    }
+}
+
+#[test]
+fn swap_test() {
+    let v = V2::new(3, -4);
+    assert_eq!(V2::new(-4, 3), v.swap());
+    assert_eq!(v, v.swap().swap());
+    assert_eq!(V2::new(3, 3), V2::new(3, 3).swap());
+}
+
+#[test]
+fn with_component_test() {
+    let v = V2::new(3, -4);
+    assert_eq!(V2::new(0, -4), v.with_x(0));
+    assert_eq!(V2::new(3, 0), v.with_y(0));
+    assert_eq!(V2::new(7, 8), v.with_x(7).with_y(8));
+    assert_eq!(V2::new(3, -4), v);
+}
+
+impl<T: Copy + Mul<Output=T>> V2<T> {
+    /// Multiplies the vector by a scalar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2::*;
+    /// let v = V2::new(3., 4.);
+    /// let u = V2::new(6., 8.);
+    /// assert_eq!(u, v.scale(2.));
+    /// ```
+    pub fn scale(&self, factor: T) -> V2<T> {
+        V2 {
+            x: factor * self.x,
+            y: factor * self.y,
+        }
+    }
+
+    /// Multiplies two vectors component-wise (the Hadamard product).
+    /// Unlike [`inner_product`](#method.inner_product), the result is a
+    /// vector, not a scalar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3, 4);
+    /// let u = V2::new(2, -1);
+    /// assert_eq!(V2::new(6, -4), v.component_mul(&u));
+    /// ```
+    pub fn component_mul(&self, other: &V2<T>) -> V2<T> {
+        V2::new(self.x * other.x, self.y * other.y)
+    }
+}
+
+impl<T: Copy + Div<Output=T>> V2<T> {
+    /// Divides two vectors component-wise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(6., 4.);
+    /// let u = V2::new(2., -8.);
+    /// assert_eq!(V2::new(3., -0.5), v.component_div(&u));
+    /// ```
+    pub fn component_div(&self, other: &V2<T>) -> V2<T> {
+        V2::new(self.x / other.x, self.y / other.y)
+    }
+}
+
+#[test]
+fn component_mul_test() {
+    let v = V2::new(1., 10.);
+    let u = V2::new(2., 4.);
+    let w = v.component_mul(&u);
+    assert_eq!(V2::new(2., 40.), w);
+    assert_eq!(v.inner_product(&u), w.x + w.y);
+    assert_eq!(v, w.component_div(&u));
+}
+
+#[test]
+fn component_div_test() {
+    let v = V2::new(7, -9);
+    let u = V2::new(2, 3);
+    assert_eq!(V2::new(3, -3), v.component_div(&u));
+}
+
+/// The primitive numeric types, as `V2` coordinates.
+///
+/// `Scalar` implies all the operator bounds that the arithmetic methods
+/// such as [`inner_product`](struct.V2.html#method.inner_product) and
+/// [`cross`](struct.V2.html#method.cross) need, so code that's generic
+/// over the coordinate type can say `T: Scalar` instead of listing each
+/// operator. The methods themselves keep their individual bounds, so
+/// they still work for other types that implement the operators.
+///
+/// This is implemented for the primitive integer and floating-point
+/// types, and it's sealed, so it can't be implemented outside this
+/// module.
+pub trait Scalar:
+    sealed::Sealed
+    + Copy
+    + Default
+    + PartialOrd
+    + Add<Output=Self>
+    + Sub<Output=Self>
+    + Mul<Output=Self>
+    + Div<Output=Self>
+{}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_scalar {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl sealed::Sealed for $t {}
+            impl Scalar for $t {}
+        )*
+    };
+}
+
+impl_scalar!(i8, i16, i32, i64, i128, isize,
+             u8, u16, u32, u64, u128, usize,
+             f32, f64);
+
+impl<T: Copy + Mul<Output=T> + Add<Output=T>> V2<T> {
+    /// Computes the inner produce (dot product) of two vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2::*;
+    /// let v = V2::new(1., 10.);
+    /// let u = V2::new(2.,  4.);
+    /// assert_eq!(42., v.inner_product(&u));
+    /// ```
+    pub fn inner_product(&self, other: &V2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Finds the square of the magnitude of a vector. This avoids the
+    /// square root, so it's cheaper for comparing lengths, and it works
+    /// for integer vectors too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3, 4);
+    /// assert_eq!(25, v.magnitude_squared());
+    /// ```
+    pub fn magnitude_squared(&self) -> T {
+        self.inner_product(self)
+    }
+}
+
+impl<T: Copy + PartialOrd + Mul<Output=T> + Add<Output=T> + Sub<Output=T>> V2<T> {
+    /// Finds the square of the distance between two points. Like
+    /// [`magnitude_squared`](#method.magnitude_squared), this works for
+    /// integer vectors, including unsigned ones: each component's
+    /// difference is taken the larger minus the smaller, so it never
+    /// underflows.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let u = V2::new(1, 1);
+    /// let v = V2::new(4, 5);
+    /// assert_eq!(25, u.distance_squared(&v));
+    /// ```
+    pub fn distance_squared(&self, other: &V2<T>) -> T {
+        fn abs_diff<T: PartialOrd + Sub<Output=T>>(a: T, b: T) -> T {
+            if a > b { a - b } else { b - a }
+        }
+
+        V2::new(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+            .magnitude_squared()
+    }
+}
+
+impl<T: Copy + Mul<Output=T> + Sub<Output=T>> V2<T> {
+    /// Computes the 2-D cross product (perp-dot product) of two
+    /// vectors, `self.x * other.y - self.y * other.x`. The result is
+    /// positive when `other` is counterclockwise from `self`, negative
+    /// when it is clockwise, and zero when they are parallel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1, 0);
+    /// let u = V2::new(0, 1);
+    /// assert_eq!(1, v.cross(&u));
+    /// assert_eq!(-1, u.cross(&v));
+    /// ```
+    pub fn cross(&self, other: &V2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+#[test]
+fn scalar_methods_test() {
+    // Bounded by `Scalar` alone, so this checks that it implies what
+    // each method needs.
+    fn products<T: Scalar>(v: V2<T>, u: V2<T>) -> (T, T, T, T, V2<T>, V2<T>) {
+        (v.inner_product(&u), v.magnitude_squared(), v.distance_squared(&u),
+         v.cross(&u), v.component_mul(&u).scale(u.x), v.max(&u))
+    }
+
+    let v: V2<i32> = V2::new(3, 4);
+    let u: V2<i32> = V2::new(-1, 2);
+    assert_eq!((5, 25, 20, 10, V2::new(3, -8), V2::new(3, 4)), products(v, u));
+
+    let v: V2<f64> = V2::new(3., 4.);
+    let u: V2<f64> = V2::new(-1., 2.);
+    assert_eq!((5., 25., 20., 10., V2::new(3., -8.), V2::new(3., 4.)), products(v, u));
+}
+
+// These take `self` by value so that they take precedence over
+// `Ord::min`, `Ord::max`, and `Ord::clamp`, which would otherwise be
+// found first for vectors whose coordinates are `Ord`.
+impl<T: Copy + PartialOrd> V2<T> {
+    /// Finds the component-wise minimum of two vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1, 5);
+    /// let u = V2::new(3, 2);
+    /// assert_eq!(V2::new(1, 2), v.min(&u));
+    /// ```
+    pub fn min(self, other: &V2<T>) -> V2<T> {
+        V2::new(partial_min(self.x, other.x), partial_min(self.y, other.y))
+    }
+
+    /// Finds the component-wise maximum of two vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(1, 5);
+    /// let u = V2::new(3, 2);
+    /// assert_eq!(V2::new(3, 5), v.max(&u));
+    /// ```
+    pub fn max(self, other: &V2<T>) -> V2<T> {
+        V2::new(partial_max(self.x, other.x), partial_max(self.y, other.y))
+    }
+
+    /// Clamps each component of the vector into the range given by the
+    /// corresponding components of `lo` and `hi`. This is useful for
+    /// keeping a point inside an axis-aligned box.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let lo = V2::new(0., 0.);
+    /// let hi = V2::new(10., 5.);
+    /// assert_eq!(V2::new(10., 3.), V2::new(12., 3.).clamp(&lo, &hi));
+    /// ```
+    pub fn clamp(self, lo: &V2<T>, hi: &V2<T>) -> V2<T> {
+        self.max(lo).min(hi)
+    }
+}
+
+// Like `std::cmp::min` and `std::cmp::max`, but only requiring
+// `PartialOrd` so they work for floats.
+fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+#[test]
+fn min_max_test() {
+    let v = V2::new(1., 5.);
+    let u = V2::new(3., 2.);
+    assert_eq!(V2::new(1., 2.), v.min(&u));
+    assert_eq!(V2::new(1., 2.), u.min(&v));
+    assert_eq!(V2::new(3., 5.), v.max(&u));
+    assert_eq!(V2::new(3., 5.), u.max(&v));
+}
+
+#[test]
+fn clamp_test() {
+    let lo = V2::new(0, 0);
+    let hi = V2::new(10, 5);
+    assert_eq!(V2::new(4, 3), V2::new(4, 3).clamp(&lo, &hi));
+    assert_eq!(V2::new(10, 0), V2::new(12, -3).clamp(&lo, &hi));
+    assert_eq!(V2::new(0, 5), V2::new(-1, 8).clamp(&lo, &hi));
+}
+
+#[test]
+fn ordering_test() {
+    use std::collections::BTreeMap;
+
+    assert!(V2::new(0, 5) < V2::new(1, 0));
+    assert!(V2::new(1, 0) < V2::new(1, 2));
+    assert_eq!(std::cmp::Ordering::Equal, V2::new(1, 2).cmp(&V2::new(1, 2)));
+
+    let mut points = vec![V2::new(2, 0), V2::new(0, 5), V2::new(0, -1)];
+    points.sort();
+    assert_eq!(vec![V2::new(0, -1), V2::new(0, 5), V2::new(2, 0)], points);
+
+    let mut map = BTreeMap::new();
+    map.insert(V2::new(1, 1), "b");
+    map.insert(V2::new(0, 9), "a");
+    assert_eq!(vec!["a", "b"], map.values().cloned().collect::<Vec<_>>());
+
+    // The component-wise methods still win over the `Ord` ones.
+    let v = V2::new(1, 5);
+    let u = V2::new(3, 2);
+    assert_eq!(V2::new(1, 2), v.min(&u));
+    assert_eq!(V2::new(3, 5), v.max(&u));
+}
+
+#[test]
+fn hash_test() {
+    use std::collections::HashMap;
+
+    let mut tiles = HashMap::new();
+    tiles.insert(V2::new(0, 0), "grass");
+    tiles.insert(V2::new(0, 1), "water");
+    tiles.insert(V2::new(1, 0), "rock");
+
+    assert_eq!(Some(&"water"), tiles.get(&V2::new(0, 1)));
+    assert_eq!(Some(&"rock"), tiles.get(&V2::new(1, 0)));
+    assert_eq!(None, tiles.get(&V2::new(1, 1)));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_test() {
+    let v = V2::new(1.5, -2.);
+    let json = serde_json::to_string(&v).unwrap();
+    assert_eq!(r#"{"x":1.5,"y":-2.0}"#, json);
+    assert_eq!(v, serde_json::from_str(&json).unwrap());
+
+    let u: V2<i32> = serde_json::from_str(r#"{"y":4,"x":3}"#).unwrap();
+    assert_eq!(V2::new(3, 4), u);
+}
+
+impl<T> V2<T>
+    where T: Copy + Default + PartialOrd + Neg<Output=T> + Add<Output=T> + Sub<Output=T>
+{
+    /// Finds the L¹ (Manhattan) norm of a vector, the sum of the absolute
+    /// values of its components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(7, V2::new(3, -4).l1_norm());
+    /// ```
+    pub fn l1_norm(&self) -> T {
+        partial_abs(self.x) + partial_abs(self.y)
+    }
+
+    /// Finds the L∞ (Chebyshev) norm of a vector, the largest absolute
+    /// value of its components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(4, V2::new(3, -4).linf_norm());
+    /// ```
+    pub fn linf_norm(&self) -> T {
+        partial_max(partial_abs(self.x), partial_abs(self.y))
+    }
+
+    /// Finds the Manhattan distance between two points, the number of
+    /// orthogonal grid steps between them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let u = V2::new(1, 1);
+    /// let v = V2::new(4, -3);
+    /// assert_eq!(7, u.manhattan_distance(&v));
+    /// ```
+    pub fn manhattan_distance(&self, other: &V2<T>) -> T {
+        (*self - *other).l1_norm()
+    }
+
+    /// Finds the Chebyshev distance between two points, the number of
+    /// grid steps between them when diagonal steps are allowed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let u = V2::new(1, 1);
+    /// let v = V2::new(4, -3);
+    /// assert_eq!(4, u.chebyshev_distance(&v));
+    /// ```
+    pub fn chebyshev_distance(&self, other: &V2<T>) -> T {
+        (*self - *other).linf_norm()
+    }
+}
+
+// Absolute value for any signed type, taking `T::default()` as zero.
+fn partial_abs<T: Copy + Default + PartialOrd + Neg<Output=T>>(a: T) -> T {
+    if a < T::default() { -a } else { a }
+}
+
+#[test]
+fn norms_test() {
+    let v = V2::new(-3i32, 2);
+    assert_eq!(5, v.l1_norm());
+    assert_eq!(3, v.linf_norm());
+    assert_eq!(0, V2::new(0i32, 0).l1_norm());
+    assert_eq!(0, V2::new(0i32, 0).linf_norm());
+
+    let u = V2::new(1.5, -2.5);
+    assert_eq!(4., u.l1_norm());
+    assert_eq!(2.5, u.linf_norm());
+}
+
+#[test]
+fn grid_distance_test() {
+    let u = V2::new(-2i32, 3);
+    let v = V2::new(1i32, -1);
+    assert_eq!(7, u.manhattan_distance(&v));
+    assert_eq!(7, v.manhattan_distance(&u));
+    assert_eq!(4, u.chebyshev_distance(&v));
+    assert_eq!(4, v.chebyshev_distance(&u));
+    assert_eq!(0, u.manhattan_distance(&u));
+}
+
+impl<T: Copy + Neg<Output=T>> V2<T> {
+    /// Rotates the vector a quarter turn counterclockwise. This doesn't
+    /// need any trigonometry, so it works for integer vectors and is
+    /// cheaper than [`rotate`](#method.rotate).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-2, 1), V2::new(1, 2).rotate_90_ccw());
+    /// ```
+    pub fn rotate_90_ccw(&self) -> V2<T> {
+        V2::new(-self.y, self.x)
+    }
+
+    /// Rotates the vector a quarter turn clockwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(2, -1), V2::new(1, 2).rotate_90_cw());
+    /// ```
+    pub fn rotate_90_cw(&self) -> V2<T> {
+        V2::new(self.y, -self.x)
+    }
+
+    /// Returns the counterclockwise perpendicular of the vector. This is
+    /// the same as [`rotate_90_ccw`](#method.rotate_90_ccw), but reads
+    /// better when computing normals from edges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let edge = V2::new(4., 0.);
+    /// assert_eq!(V2::new(0., 1.), edge.perp().normalize());
+    /// ```
+    pub fn perp(&self) -> V2<T> {
+        self.rotate_90_ccw()
+    }
+
+    /// Negates the vector in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let mut v = V2::new(1, -2);
+    /// v.negate();
+    /// assert_eq!(V2::new(-1, 2), v);
+    /// ```
+    pub fn negate(&mut self) {
+        self.x = -self.x;
+        self.y = -self.y;
+    }
+
+    /// Reflects the vector across the x axis by negating its y
+    /// component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(1, -2), V2::new(1, 2).reflect_x());
+    /// ```
+    pub fn reflect_x(&self) -> V2<T> {
+        V2::new(self.x, -self.y)
+    }
+
+    /// Reflects the vector across the y axis by negating its x
+    /// component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-1, 2), V2::new(1, 2).reflect_y());
+    /// ```
+    pub fn reflect_y(&self) -> V2<T> {
+        V2::new(-self.x, self.y)
+    }
+}
+
+impl V2<f64> {
+    /// Constructs a vector from polar coordinates. The angle is in
+    /// radians, measured counterclockwise from the positive x axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = V2::from_polar(2., PI / 2.);
+    /// assert!(v.approx_eq(&V2::new(0., 2.), 1e-12));
+    /// ```
+    pub fn from_polar(radius: f64, angle_rad: f64) -> V2<f64> {
+        V2::new(radius * angle_rad.cos(), radius * angle_rad.sin())
+    }
+
+    /// Finds the angle of a vector in radians, measured counterclockwise
+    /// from the positive x axis. The result is in the range `[-π, π]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// assert_eq!(PI / 2., V2::new(0., 5.).angle());
+    /// assert_eq!(-PI / 4., V2::new(1., -1.).angle());
+    /// ```
+    pub fn angle(&self) -> f64 {
+        self.y.atan2(self.x)
+    }
+
+    /// Rotates the vector counterclockwise by the given angle in
+    /// radians.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = V2::new(1., 0.).rotate(PI / 4.);
+    /// let s = 0.5f64.sqrt();
+    /// assert!(v.approx_eq(&V2::new(s, s), 1e-12));
+    /// ```
+    pub fn rotate(&self, angle_rad: f64) -> V2<f64> {
+        let (sin, cos) = angle_rad.sin_cos();
+        V2::new(cos * self.x - sin * self.y, sin * self.x + cos * self.y)
+    }
+
+    /// Finds the unsigned angle between two vectors in radians, in
+    /// `[0, π]`.
+    ///
+    /// The cosine is clamped to `[-1, 1]` before taking its arccosine, so
+    /// rounding error for nearly parallel vectors can't produce `NaN`.
+    /// If either vector is zero, the angle is undefined and the result
+    /// is `NaN`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = V2::new(1., 1.);
+    /// let u = V2::new(-1., 1.);
+    /// assert!((v.angle_between(&u) - PI / 2.).abs() < 1e-12);
+    /// ```
+    pub fn angle_between(&self, other: &V2<f64>) -> f64 {
+        let cos = self.inner_product(other) / (self.magnitude() * other.magnitude());
+        cos.clamp(-1., 1.).acos()
+    }
+
+    /// Finds the signed angle in radians that rotates the direction of
+    /// `self` onto the direction of `other`, in `(-π, π]`. The angle is
+    /// positive when `other` is counterclockwise from `self` and
+    /// negative when it is clockwise, matching the sign of
+    /// [`cross`](#method.cross).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// use std::f64::consts::PI;
+    ///
+    /// let v = V2::new(1., 0.);
+    /// assert_eq!(PI / 2., v.signed_angle_to(&V2::new(0., 1.)));
+    /// assert_eq!(-PI / 2., v.signed_angle_to(&V2::new(0., -1.)));
+    /// ```
+    pub fn signed_angle_to(&self, other: &V2<f64>) -> f64 {
+        use std::f64::consts::PI;
+
+        let angle = self.cross(other).atan2(self.inner_product(other));
+        // For opposite vectors the cross product can be `-0.`, which
+        // makes `atan2` return `-π`; report those as `π`.
+        if angle == -PI { PI } else { angle }
+    }
+
+    /// Linearly interpolates between two vectors. When `t` is 0 this
+    /// returns `self`, and when `t` is 1 it returns `other`; values of
+    /// `t` outside `[0, 1]` extrapolate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(0., 10.);
+    /// let u = V2::new(4., 20.);
+    /// assert_eq!(V2::new(1., 12.5), v.lerp(&u, 0.25));
+    /// ```
+    pub fn lerp(&self, other: &V2<f64>, t: f64) -> V2<f64> {
+        self + (other - self).scale(t)
+    }
+
+    /// Like [`lerp`](#method.lerp), but clamps `t` into `[0, 1]` so the
+    /// result always lies between `self` and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(0., 10.);
+    /// let u = V2::new(4., 20.);
+    /// assert_eq!(u, v.lerp_clamped(&u, 1.5));
+    /// ```
+    pub fn lerp_clamped(&self, other: &V2<f64>, t: f64) -> V2<f64> {
+        self.lerp(other, t.clamp(0., 1.))
+    }
+
+    /// Finds the point halfway between two points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let u = V2::new(0., 0.);
+    /// let v = V2::new(2., 4.);
+    /// assert_eq!(V2::new(1., 2.), u.midpoint(&v));
+    /// ```
+    pub fn midpoint(&self, other: &V2<f64>) -> V2<f64> {
+        (self + other).scale(0.5)
+    }
+
+    /// Projects `self` onto `other`, giving the component of `self` that
+    /// is parallel to `other`.
+    ///
+    /// Projecting onto the zero vector divides by zero and produces
+    /// `NaN` components; see
+    /// [`project_onto_or_zero`](#method.project_onto_or_zero).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3., 3.);
+    /// let x_axis = V2::new(2., 0.);
+    /// assert_eq!(V2::new(3., 0.), v.project_onto(&x_axis));
+    /// ```
+    pub fn project_onto(&self, other: &V2<f64>) -> V2<f64> {
+        other.scale(self.inner_product(other) / other.inner_product(other))
+    }
+
+    /// Like [`project_onto`](#method.project_onto), but returns the zero
+    /// vector when `other` is zero.
+    pub fn project_onto_or_zero(&self, other: &V2<f64>) -> V2<f64> {
+        if other.magnitude_squared() == 0. {
+            V2::new(0., 0.)
+        } else {
+            self.project_onto(other)
+        }
+    }
+
+    /// Finds the component of `self` perpendicular to `other`, which is
+    /// what's left after subtracting the projection onto `other`.
+    ///
+    /// Like `project_onto`, this produces `NaN` components when `other`
+    /// is zero; see [`reject_from_or_zero`](#method.reject_from_or_zero).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3., 3.);
+    /// let x_axis = V2::new(2., 0.);
+    /// assert_eq!(V2::new(0., 3.), v.reject_from(&x_axis));
+    /// ```
+    pub fn reject_from(&self, other: &V2<f64>) -> V2<f64> {
+        *self - self.project_onto(other)
+    }
+
+    /// Like [`reject_from`](#method.reject_from), but treats a zero
+    /// `other` as having no parallel component, so it returns `self`.
+    pub fn reject_from_or_zero(&self, other: &V2<f64>) -> V2<f64> {
+        *self - self.project_onto_or_zero(other)
+    }
+
+    /// Reflects the vector off a surface with the given normal, computing
+    /// `v - 2 (v · n) n`.
+    ///
+    /// This assumes `normal` is a unit vector. If it isn't, the result
+    /// is scaled incorrectly; normalize it first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let velocity = V2::new(1., -1.);
+    /// let floor = V2::new(0., 1.);
+    /// assert_eq!(V2::new(1., 1.), velocity.reflect(&floor));
+    /// ```
+    pub fn reflect(&self, normal: &V2<f64>) -> V2<f64> {
+        *self - normal.scale(2. * self.inner_product(normal))
+    }
+
+    /// Takes the absolute value of each component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(1.5, 2.), V2::new(-1.5, 2.).abs());
+    /// ```
+    pub fn abs(&self) -> V2<f64> {
+        self.map(f64::abs)
+    }
+
+    /// Takes the sign of each component, as given by `f64::signum`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-1., 1.), V2::new(-1.5, 2.).signum());
+    /// ```
+    pub fn signum(&self) -> V2<f64> {
+        self.map(f64::signum)
+    }
+
+    /// Rounds each component down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-2., 2.), V2::new(-1.5, 2.7).floor());
+    /// ```
+    pub fn floor(&self) -> V2<f64> {
+        self.map(f64::floor)
+    }
+
+    /// Rounds each component up.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-1., 3.), V2::new(-1.5, 2.2).ceil());
+    /// ```
+    pub fn ceil(&self) -> V2<f64> {
+        self.map(f64::ceil)
+    }
+
+    /// Rounds each component to the nearest integer, with halfway cases
+    /// rounded away from zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(-2., 2.), V2::new(-1.5, 2.2).round());
+    /// ```
+    pub fn round(&self) -> V2<f64> {
+        self.map(f64::round)
+    }
+
+    /// Finds the magnitude (Euclidean length) of a vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3., 4.);
+    /// assert_eq!(5., v.magnitude());
+    /// ```
+    pub fn magnitude(&self) -> f64 {
+        self.inner_product(self).sqrt()
+    }
+
+    /// Returns the unit vector pointing in the same direction.
+    ///
+    /// The zero vector has no direction, so normalizing it divides by
+    /// zero and produces `NaN` components. Use
+    /// [`normalize_or_zero`](#method.normalize_or_zero) if the input
+    /// might be zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3., 4.);
+    /// assert_eq!(V2::new(0.6, 0.8), v.normalize());
+    /// assert!(V2::new(0., 0.).normalize().x.is_nan());
+    /// ```
+    pub fn normalize(&self) -> V2<f64> {
+        let magnitude = self.magnitude();
+        V2::new(self.x / magnitude, self.y / magnitude)
+    }
+
+    /// Returns the unit vector pointing in the same direction, or the
+    /// zero vector if `self` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(3., 4.);
+    /// assert_eq!(V2::new(0.6, 0.8), v.normalize_or_zero());
+    /// assert_eq!(V2::new(0., 0.), V2::new(0., 0.).normalize_or_zero());
+    /// ```
+    pub fn normalize_or_zero(&self) -> V2<f64> {
+        if self.magnitude() == 0. {
+            V2::new(0., 0.)
+        } else {
+            self.normalize()
+        }
+    }
+
+    /// Limits the magnitude of a vector to `max`. A vector no longer than
+    /// `max` is returned unchanged, and a longer one is scaled down to
+    /// length `max`, keeping its direction.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `max` is negative or NaN, which would
+    /// otherwise flip the vector or turn it into NaNs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(6., 8.);
+    /// assert_eq!(V2::new(3., 4.), v.clamp_magnitude(5.));
+    /// assert_eq!(v, v.clamp_magnitude(20.));
+    /// ```
+    pub fn clamp_magnitude(&self, max: f64) -> V2<f64> {
+        debug_assert!(max >= 0., "clamp_magnitude: max {} is negative or NaN", max);
+
+        let magnitude = self.magnitude();
+        if magnitude <= max || magnitude == 0. {
+            *self
+        } else {
+            self.scale(max / magnitude)
+        }
+    }
+
+    /// Finds the distance between two points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let u = V2::new(0., 0.);
+    /// let v = V2::new(3., 4.);
+    /// assert_eq!(5., u.distance(&v));
+    /// ```
+    pub fn distance(&self, other: &V2<f64>) -> f64 {
+        (self - other).magnitude()
+    }
+
+    /// Checks whether two vectors are equal to within `epsilon` in each
+    /// component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// let v = V2::new(0.1 + 0.2, 1.);
+    /// let u = V2::new(0.3, 1.);
+    /// assert_ne!(u, v);
+    /// assert!(u.approx_eq(&v, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &V2<f64>, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon && (self.y - other.y).abs() <= epsilon
+    }
+}
+
+#[test]
+fn magnitude_test() {
+    let v = V2::new(3., 4.);
+    assert_eq!(25., v.magnitude_squared());
+    assert_eq!(v.magnitude_squared(), v.magnitude() * v.magnitude());
+    assert_eq!(0., V2::new(0., 0.).magnitude());
+}
+
+#[test]
+fn normalize_test() {
+    let v = V2::new(0., -2.);
+    assert_eq!(V2::new(0., -1.), v.normalize());
+    assert_eq!(V2::new(0., -1.), v.normalize_or_zero());
+    assert!((V2::new(5., 7.).normalize().magnitude() - 1.).abs() < 1e-12);
+}
+
+#[test]
+fn clamp_magnitude_test() {
+    let v = V2::new(-6., 8.);
+    assert_eq!(V2::new(-3., 4.), v.clamp_magnitude(5.));
+    assert_eq!(v, v.clamp_magnitude(10.));
+    assert_eq!(v, v.clamp_magnitude(20.));
+    assert_eq!(V2::new(0., 0.), v.clamp_magnitude(0.));
+
+    let u = V2::new(5., 7.).clamp_magnitude(2.);
+    assert!((u.magnitude() - 2.).abs() < 1e-12);
+    assert!(u.approx_eq(&V2::new(5., 7.).normalize().scale(2.), 1e-12));
+
+    let zero = V2::new(0., 0.);
+    assert_eq!(zero, zero.clamp_magnitude(1.));
+    assert_eq!(zero, zero.clamp_magnitude(0.));
 }
 
-impl<T: Copy + Mul<Output=T>> V2<T> {
-    /// Multiplies the vector by a scalar.
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "is negative or NaN")]
+fn clamp_magnitude_negative_max_test() {
+    V2::new(3., 4.).clamp_magnitude(-1.);
+}
+
+#[test]
+fn normalize_zero_test() {
+    let zero = V2::new(0., 0.);
+    let n = zero.normalize();
+    assert!(n.x.is_nan() && n.y.is_nan());
+    assert_eq!(zero, zero.normalize_or_zero());
+}
+
+#[test]
+fn distance_test() {
+    let u = V2::new(-1., 2.);
+    let v = V2::new(2., -2.);
+    assert_eq!(5., u.distance(&v));
+    assert_eq!(5., v.distance(&u));
+    assert_eq!(25., u.distance_squared(&v));
+    assert_eq!(0., u.distance(&u));
+
+    let p = V2::new(-3i64, 0);
+    let q = V2::new(0i64, 4);
+    assert_eq!(25, p.distance_squared(&q));
+
+    let p = V2::new(1u32, 5);
+    let q = V2::new(4u32, 1);
+    assert_eq!(25, p.distance_squared(&q));
+    assert_eq!(25, q.distance_squared(&p));
+}
+
+#[test]
+fn cross_test() {
+    let u = V2::new(2i64, 1);
+    let v = V2::new(1i64, 3);
+    assert_eq!(5, u.cross(&v));
+    assert_eq!(-5, v.cross(&u));
+    assert_eq!(0, u.cross(&u));
+    assert_eq!(0, u.cross(&V2::new(-4, -2)));
+
+    let a = V2::new(1.5, 0.);
+    let b = V2::new(0., 2.);
+    assert_eq!(3., a.cross(&b));
+    assert_eq!(-3., b.cross(&a));
+}
+
+#[test]
+fn approx_eq_test() {
+    let v = V2::new(1., 2.);
+    let u = V2::new(1. + 1e-10, 2. - 1e-10);
+    assert_ne!(v, u);
+    assert!(v.approx_eq(&u, 1e-9));
+    assert!(!v.approx_eq(&u, 1e-11));
+    assert!(!v.approx_eq(&V2::new(1., 2.1), 0.05));
+}
+
+#[test]
+fn polar_test() {
+    use std::f64::consts::PI;
+
+    for &(r, theta) in &[(1., 0.), (2., PI / 3.), (0.5, -2.), (10., 3.)] {
+        let v = V2::from_polar(r, theta);
+        assert!((v.magnitude() - r).abs() < 1e-12);
+        assert!((v.angle() - theta).abs() < 1e-12);
+    }
+
+    assert_eq!(0., V2::new(1., 0.).angle());
+    assert_eq!(PI, V2::new(-1., 0.).angle());
+}
+
+#[test]
+fn rotate_test() {
+    use std::f64::consts::PI;
+
+    let v = V2::new(3., -2.);
+    assert!(v.rotate(PI / 2.).approx_eq(&v.rotate_90_ccw(), 1e-12));
+    assert!(v.rotate(-PI / 2.).approx_eq(&v.rotate_90_cw(), 1e-12));
+    assert!(v.rotate(2. * PI).approx_eq(&v, 1e-12));
+    assert!((v.rotate(1.).magnitude() - v.magnitude()).abs() < 1e-12);
+}
+
+#[test]
+fn angle_between_test() {
+    use std::f64::consts::PI;
+
+    let v = V2::new(3., 0.);
+    assert_eq!(PI / 2., v.angle_between(&V2::new(0., 2.)));
+    assert_eq!(PI / 2., v.angle_between(&V2::new(0., -2.)));
+    assert_eq!(PI, v.angle_between(&V2::new(-1., 0.)));
+
+    // For these the cosine rounds to slightly more than 1 or less than
+    // -1, which would make an unclamped `acos` return `NaN`.
+    let u = V2::new(0.1, 0.7);
+    assert_eq!(0., u.angle_between(&u));
+    assert_eq!(0., u.angle_between(&u.scale(3.)));
+    assert_eq!(PI, u.angle_between(&-u));
+
+    assert!(v.angle_between(&V2::new(0., 0.)).is_nan());
+}
+
+#[test]
+fn signed_angle_to_test() {
+    use std::f64::consts::PI;
+
+    let v = V2::new(3., 1.);
+    let ccw = v.rotate_90_ccw();
+    assert!((v.signed_angle_to(&ccw) - PI / 2.).abs() < 1e-12);
+    assert!((ccw.signed_angle_to(&v) + PI / 2.).abs() < 1e-12);
+
+    for &theta in &[0.25, 1., 2., -0.5, -3.] {
+        let u = v.rotate(theta).scale(2.);
+        assert!((v.signed_angle_to(&u) - theta).abs() < 1e-12);
+        assert!((v.signed_angle_to(&u).abs() - v.angle_between(&u)).abs() < 1e-12);
+    }
+
+    assert_eq!(PI, V2::new(1., 0.).signed_angle_to(&V2::new(-1., 0.)));
+    assert_eq!(PI, V2::new(-1., 0.).signed_angle_to(&V2::new(1., 0.)));
+    assert_eq!(PI, V2::new(0., 1.).signed_angle_to(&V2::new(0., -1.)));
+    assert_eq!(PI, v.signed_angle_to(&-v));
+    assert_eq!(0., v.signed_angle_to(&v));
+}
+
+#[test]
+fn lerp_test() {
+    let v = V2::new(1., -2.);
+    let u = V2::new(3., 6.);
+    assert_eq!(v, v.lerp(&u, 0.));
+    assert_eq!(u, v.lerp(&u, 1.));
+    assert_eq!(V2::new(2., 2.), v.lerp(&u, 0.5));
+    assert_eq!(V2::new(5., 14.), v.lerp(&u, 2.));
+}
+
+#[test]
+fn lerp_clamped_test() {
+    let v = V2::new(1., -2.);
+    let u = V2::new(3., 6.);
+    assert_eq!(V2::new(2., 2.), v.lerp_clamped(&u, 0.5));
+    assert_eq!(u, v.lerp_clamped(&u, 2.));
+    assert_eq!(v, v.lerp_clamped(&u, -1.));
+}
+
+#[test]
+fn midpoint_test() {
+    let v = V2::new(1., -2.);
+    let u = V2::new(3., 6.);
+    assert_eq!(V2::new(2., 2.), v.midpoint(&u));
+    assert_eq!(v.midpoint(&u), u.midpoint(&v));
+    assert_eq!(v.lerp(&u, 0.5), v.midpoint(&u));
+}
+
+#[test]
+fn project_test() {
+    let v = V2::new(3., 3.);
+    let x_axis = V2::new(1., 0.);
+    let p = v.project_onto(&x_axis);
+    let r = v.reject_from(&x_axis);
+    assert_eq!(V2::new(3., 0.), p);
+    assert_eq!(V2::new(0., 3.), r);
+    assert_eq!(0., r.inner_product(&x_axis));
+    assert_eq!(v, p + r);
+
+    let diagonal = V2::new(1., 1.);
+    let u = V2::new(2., 0.);
+    assert!(u.project_onto(&diagonal).approx_eq(&V2::new(1., 1.), 1e-12));
+    assert!(u.reject_from(&diagonal).inner_product(&diagonal).abs() < 1e-12);
+}
+
+#[test]
+fn project_zero_test() {
+    let v = V2::new(3., 3.);
+    let zero = V2::new(0., 0.);
+    assert!(v.project_onto(&zero).x.is_nan());
+    assert!(v.reject_from(&zero).x.is_nan());
+    assert_eq!(zero, v.project_onto_or_zero(&zero));
+    assert_eq!(v, v.reject_from_or_zero(&zero));
+}
+
+#[test]
+fn reflect_test() {
+    let down = V2::new(0., -3.);
+    let up = V2::new(0., 1.);
+    assert_eq!(V2::new(0., 3.), down.reflect(&up));
+
+    let velocity = V2::new(2., -1.);
+    assert_eq!(V2::new(2., 1.), velocity.reflect(&up));
+    assert_eq!(velocity.reflect_x(), velocity.reflect(&up));
+
+    let wall = V2::new(-1., 1.).normalize();
+    let v = V2::new(1., 0.);
+    assert!(v.reflect(&wall).approx_eq(&V2::new(0., 1.), 1e-12));
+}
+
+#[test]
+fn abs_test() {
+    assert_eq!(V2::new(3., 0.5), V2::new(-3., 0.5).abs());
+}
+
+#[test]
+fn signum_test() {
+    assert_eq!(V2::new(1., -1.), V2::new(0.25, -7.).signum());
+}
+
+#[test]
+fn floor_test() {
+    assert_eq!(V2::new(0., -8.), V2::new(0.75, -7.25).floor());
+}
+
+#[test]
+fn ceil_test() {
+    assert_eq!(V2::new(1., -7.), V2::new(0.75, -7.25).ceil());
+}
+
+#[test]
+fn round_test() {
+    assert_eq!(V2::new(1., -7.), V2::new(0.75, -7.25).round());
+}
+
+#[test]
+fn rotate_90_test() {
+    let v = V2::new(3, -2);
+    assert_eq!(v, v.rotate_90_ccw().rotate_90_cw());
+    assert_eq!(-v, v.rotate_90_ccw().rotate_90_ccw());
+    assert_eq!(0, v.inner_product(&v.rotate_90_ccw()));
+}
+
+#[test]
+fn reflect_axes_test() {
+    let v = V2::new(3, -2);
+    assert_eq!(V2::new(3, 2), v.reflect_x());
+    assert_eq!(V2::new(-3, -2), v.reflect_y());
+    assert_eq!(v, v.reflect_x().reflect_x());
+    assert_eq!(-v, v.reflect_x().reflect_y());
+}
+
+#[test]
+fn perp_test() {
+    let v = V2::new(3, -2);
+    assert_eq!(V2::new(2, 3), v.perp());
+    assert_eq!(0, v.inner_product(&v.perp()));
+    assert!(v.cross(&v.perp()) > 0);
+
+    let u = V2::new(0.5, 7.);
+    assert_eq!(0., u.inner_product(&u.perp()));
+}
+
+#[test]
+fn negate_test() {
+    let v = V2::new(3., -0.5);
+    let mut u = v;
+    u.negate();
+    assert_eq!(-v, u);
+    u.negate();
+    assert_eq!(v, u);
+}
+
+impl<T: Copy + Default> V2<T> {
+    /// Returns the zero vector, with both components `T::default()`.
     ///
     /// # Example
     ///
     /// ```
-    /// # use intro::v2::*;
-    /// let v = V2::new(3., 4.);
-    /// let u = V2::new(6., 8.);
-    /// assert_eq!(u, v.scale(2.));
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(0., 0.), V2::<f64>::zero());
+    /// assert_eq!(V2::new(0, 0), V2::<i32>::zero());
     /// ```
-    pub fn scale(&self, factor: T) -> V2<T> {
-        V2 {
-            x: factor * self.x,
-            y: factor * self.y,
-        }
+    pub fn zero() -> Self {
+        V2::default()
     }
 }
 
-impl<T: Copy + Mul<Output=T> + Add<Output=T>> V2<T> {
-    /// Computes the inner produce (dot product) of two vectors.
+impl V2<f64> {
+    /// Returns the unit vector along the x axis.
     ///
     /// # Example
     ///
     /// ```
-    /// # use intro::v2::*;
-    /// let v = V2::new(1., 10.);
-    /// let u = V2::new(2.,  4.);
-    /// assert_eq!(42., v.inner_product(&u));
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(1.0, 0.0), V2::unit_x());
     /// ```
-    pub fn inner_product(&self, other: &V2<T>) -> T {
-        self.x * other.x + self.y * other.y
+    pub fn unit_x() -> Self {
+        V2::new(1., 0.)
+    }
+
+    /// Returns the unit vector along the y axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(0.0, 1.0), V2::unit_y());
+    /// ```
+    pub fn unit_y() -> Self {
+        V2::new(0., 1.)
     }
 }
 
+#[test]
+fn named_constructors_test() {
+    assert_eq!(V2::default(), V2::<usize>::zero());
+    assert_eq!(1., V2::unit_x().magnitude());
+    assert_eq!(1., V2::unit_y().magnitude());
+    assert_eq!(V2::unit_y(), V2::unit_x().perp());
+}
+
 impl<T: Copy + Default> Default for V2<T> {
     fn default() -> Self {
         V2::new(T::default(), T::default())
@@ -77,9 +1476,15 @@ fn test_default() {
     assert_eq!(V2::new(0, 0), v);
 }
 
+// Formatting flags such as precision and width are passed through to
+// each component, so `format!("{:.1}", v)` rounds both coordinates.
 impl<T: fmt::Display> fmt::Display for V2<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "⟨{}, {}⟩", self.x, self.y)
+        fmt.write_str("⟨")?;
+        self.x.fmt(fmt)?;
+        fmt.write_str(", ")?;
+        self.y.fmt(fmt)?;
+        fmt.write_str("⟩")
     }
 }
 
@@ -89,6 +1494,15 @@ fn test_display() {
     assert_eq!("⟨3, 4⟩", v.to_string());
 }
 
+#[test]
+fn test_display_flags() {
+    let v = V2::new(1.234, 5.678);
+    assert_eq!("⟨1.234, 5.678⟩", format!("{}", v));
+    assert_eq!("⟨1.2, 5.7⟩", format!("{:.1}", v));
+    assert_eq!("⟨ 1.23,  5.68⟩", format!("{:5.2}", v));
+    assert_eq!("⟨+3, -4⟩", format!("{:+}", V2::new(3, -4)));
+}
+
 impl<T: Neg> Neg for V2<T> {
     /// The result of negating a vector is a vector.
     type Output = V2<T::Output>;
@@ -209,4 +1623,368 @@ impl<'a, 'b, T, U> Sub<&'b V2<U>> for &'a V2<T>
     }
 }
 
+impl<T: AddAssign> AddAssign for V2<T> {
+    /// Adds a vector to this one in place.
+    fn add_assign(&mut self, other: V2<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Copy + AddAssign> AddAssign<&V2<T>> for V2<T> {
+    /// Adds a vector to this one in place.
+    fn add_assign(&mut self, other: &V2<T>) {
+        *self += *other;
+    }
+}
+
+impl<T: SubAssign> SubAssign for V2<T> {
+    /// Subtracts a vector from this one in place.
+    fn sub_assign(&mut self, other: V2<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Copy + SubAssign> SubAssign<&V2<T>> for V2<T> {
+    /// Subtracts a vector from this one in place.
+    fn sub_assign(&mut self, other: &V2<T>) {
+        *self -= *other;
+    }
+}
+
+#[test]
+fn add_assign_test() {
+    let u = V2::new(1., 2.);
+    let v = V2::new(10., 20.);
+
+    let mut w = u;
+    w += v;
+    assert_eq!(u + v, w);
+
+    let mut w = u;
+    w += &v;
+    assert_eq!(u + v, w);
+}
+
+#[test]
+fn sub_assign_test() {
+    let u = V2::new(1, 2);
+    let v = V2::new(10, 20);
+
+    let mut w = u;
+    w -= v;
+    assert_eq!(u - v, w);
+
+    let mut w = u;
+    w -= &v;
+    assert_eq!(u - v, w);
+}
+
+// Multiplication by a scalar, with the scalar on the left. The orphan
+// rule lets us implement `Mul` for the foreign type `f64` because `V2`
+// is local to this crate.
+impl Mul<V2<f64>> for f64 {
+    /// The result of multiplying a vector by a scalar.
+    type Output = V2<f64>;
+
+    /// Multiplies a vector by a scalar.
+    fn mul(self, other: V2<f64>) -> V2<f64> {
+        other.scale(self)
+    }
+}
+
+impl Mul<&V2<f64>> for f64 {
+    /// The result of multiplying a vector by a scalar.
+    type Output = V2<f64>;
+
+    /// Multiplies a vector by a scalar.
+    fn mul(self, other: &V2<f64>) -> V2<f64> {
+        other.scale(self)
+    }
+}
+
+#[test]
+fn mul_test() {
+    let v = V2::new(3., 4.);
+    let u = V2::new(6., 8.);
+    assert_eq!(u, 2. * V2::new(3., 4.));
+    assert_eq!(u, 2. * &v);
+    assert_eq!(v.scale(2.), 2. * v);
+}
+
+// Division by a scalar, the counterpart of `scale`.
+impl Div<f64> for V2<f64> {
+    /// The result of dividing a vector by a scalar is a vector.
+    type Output = V2<f64>;
+
+    /// Divides a vector by a scalar.
+    ///
+    /// This follows IEEE semantics, so dividing by zero yields
+    /// infinities (or `NaN` for a zero component) rather than
+    /// panicking.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use intro::v2generic::*;
+    /// assert_eq!(V2::new(3., 4.), V2::new(6., 8.) / 2.);
+    ///
+    /// let v = V2::new(1., -1.) / 0.;
+    /// assert_eq!(V2::new(f64::INFINITY, f64::NEG_INFINITY), v);
+    /// ```
+    fn div(self, divisor: f64) -> V2<f64> {
+        V2::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl Div<f64> for &V2<f64> {
+    /// The result of dividing a vector by a scalar is a vector.
+    type Output = V2<f64>;
+
+    /// Divides a vector by a scalar.
+    fn div(self, divisor: f64) -> V2<f64> {
+        *self / divisor
+    }
+}
+
+impl DivAssign<f64> for V2<f64> {
+    /// Divides this vector by a scalar in place.
+    fn div_assign(&mut self, divisor: f64) {
+        *self = *self / divisor;
+    }
+}
+
+#[test]
+fn div_test() {
+    let v = V2::new(6., 8.);
+    let u = V2::new(3., 4.);
+    assert_eq!(u, v / 2.);
+    assert_eq!(u, &v / 2.);
+    assert_eq!(v.scale(0.5), v / 2.);
+
+    let mut w = v;
+    w /= 2.;
+    assert_eq!(u, w);
+}
+
+#[test]
+fn div_by_zero_test() {
+    let v = V2::new(1., -1.) / 0.;
+    assert_eq!(V2::new(f64::INFINITY, f64::NEG_INFINITY), v);
+
+    let w = V2::new(0., 0.) / 0.;
+    assert!(w.x.is_nan() && w.y.is_nan());
+}
+
+impl<T> Index<usize> for V2<T> {
+    type Output = T;
+
+    /// Gets a component by axis number: 0 for `x` and 1 for `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is not 0 or 1.
+    fn index(&self, axis: usize) -> &T {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("V2::index: axis {} out of range (must be 0 or 1)", axis),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for V2<T> {
+    /// Gets a mutable reference to a component by axis number: 0 for `x`
+    /// and 1 for `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `axis` is not 0 or 1.
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        match axis {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("V2::index_mut: axis {} out of range (must be 0 or 1)", axis),
+        }
+    }
+}
+
+#[test]
+fn index_test() {
+    let mut v = V2::new(3, 4);
+    assert_eq!(3, v[0]);
+    assert_eq!(4, v[1]);
+
+    v[0] = 5;
+    v[1] += 2;
+    assert_eq!(V2::new(5, 6), v);
+
+    let w = V2::new(2, -1);
+    let mut acc = 0;
+    for i in 0 .. 2 {
+        acc += v[i] * w[i];
+    }
+    assert_eq!(v.inner_product(&w), acc);
+}
+
+#[test]
+#[should_panic(expected = "axis 2 out of range")]
+fn index_out_of_range_test() {
+    let v = V2::new(3, 4);
+    let _ = v[2];
+}
+
+#[test]
+#[should_panic(expected = "axis 2 out of range")]
+fn index_mut_out_of_range_test() {
+    let mut v = V2::new(3, 4);
+    v[2] = 0;
+}
+
+impl<T> From<(T, T)> for V2<T> {
+    /// Converts an `(x, y)` pair into a vector.
+    fn from((x, y): (T, T)) -> Self {
+        V2::new(x, y)
+    }
+}
+
+impl<T> From<V2<T>> for (T, T) {
+    /// Converts a vector into an `(x, y)` pair.
+    fn from(v: V2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+#[test]
+fn tuple_conversion_test() {
+    let v: V2<f64> = (1., 2.).into();
+    assert_eq!(V2::new(1., 2.), v);
+
+    let (x, y) = v.into();
+    assert_eq!((1., 2.), (x, y));
+
+    let pair: (i32, i32) = V2::from((3, 4)).into();
+    assert_eq!((3, 4), pair);
+}
+
+impl<T> IntoIterator for V2<T> {
+    type Item = T;
+    type IntoIter = std::array::IntoIter<T, 2>;
+
+    /// Iterates over the components, `x` then `y`.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.to_array())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a V2<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
 
+    /// Iterates over references to the components, `x` then `y`.
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_array().iter()
+    }
+}
+
+#[test]
+fn into_iter_test() {
+    let v = V2::new(3, 4);
+
+    let components: Vec<i32> = v.into_iter().collect();
+    assert_eq!(vec![3, 4], components);
+
+    let refs: Vec<&i32> = (&v).into_iter().collect();
+    assert_eq!(vec![&3, &4], refs);
+
+    let mut sum = 0;
+    for c in v {
+        sum += c;
+    }
+    assert_eq!(7, sum);
+    assert_eq!(7, v.into_iter().sum::<i32>());
+}
+
+impl<T: Copy + Default + Add<Output=T>> Sum for V2<T> {
+    /// Adds up a sequence of vectors, starting from `V2::default()`.
+    fn sum<I: Iterator<Item = V2<T>>>(iter: I) -> Self {
+        iter.fold(V2::default(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, T: Copy + Default + Add<Output=T>> Sum<&'a V2<T>> for V2<T> {
+    /// Adds up a sequence of vectors, starting from `V2::default()`.
+    fn sum<I: Iterator<Item = &'a V2<T>>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+#[test]
+fn sum_test() {
+    let points = vec![V2::new(1., 2.), V2::new(10., 20.), V2::new(100., 200.)];
+    let total = points[0] + points[1] + points[2];
+
+    assert_eq!(total, points.iter().sum::<V2<f64>>());
+    assert_eq!(total, points.into_iter().sum::<V2<f64>>());
+    assert_eq!(V2::new(0, 0), Vec::<V2<i32>>::new().into_iter().sum());
+}
+
+/// Finds the centroid (mean) of a set of points, or `None` if there are
+/// no points.
+///
+/// # Example
+///
+/// ```
+/// # use intro::v2generic::*;
+/// let points = [V2::new(0., 0.), V2::new(4., 0.), V2::new(2., 3.)];
+/// assert_eq!(Some(V2::new(2., 1.)), centroid(&points));
+/// assert_eq!(None, centroid(&[]));
+/// ```
+pub fn centroid(points: &[V2<f64>]) -> Option<V2<f64>> {
+    if points.is_empty() {
+        None
+    } else {
+        Some(points.iter().sum::<V2<f64>>() / points.len() as f64)
+    }
+}
+
+#[test]
+fn centroid_test() {
+    assert_eq!(None, centroid(&[]));
+    assert_eq!(Some(V2::new(3., -1.)), centroid(&[V2::new(3., -1.)]));
+
+    let square = [
+        V2::new(1., 1.), V2::new(3., 1.), V2::new(3., 3.), V2::new(1., 3.),
+    ];
+    assert_eq!(Some(V2::new(2., 2.)), centroid(&square));
+}
+
+/// Finds the signed area of the triangle with vertices `a`, `b`, and
+/// `c`. The area is positive when the vertices wind counterclockwise,
+/// negative when they wind clockwise, and zero when they are collinear.
+///
+/// # Example
+///
+/// ```
+/// # use intro::v2generic::*;
+/// let a = V2::new(0., 0.);
+/// let b = V2::new(4., 0.);
+/// let c = V2::new(0., 3.);
+/// assert_eq!(6., signed_area(&a, &b, &c));
+/// assert_eq!(-6., signed_area(&a, &c, &b));
+/// ```
+pub fn signed_area(a: &V2<f64>, b: &V2<f64>, c: &V2<f64>) -> f64 {
+    0.5 * (b - a).cross(&(c - a))
+}
+
+#[test]
+fn signed_area_test() {
+    let a = V2::new(1., 1.);
+    let b = V2::new(3., 1.);
+    let c = V2::new(3., 4.);
+    assert_eq!(3., signed_area(&a, &b, &c));
+    assert_eq!(3., signed_area(&b, &c, &a));
+    assert_eq!(-3., signed_area(&c, &b, &a));
+    assert_eq!(0., signed_area(&a, &b, &V2::new(7., 1.)));
+}
@@ -1,4 +1,7 @@
+use ngrams::NGrams;
+
 use std::cmp;
+use std::iter::{Product, Sum};
 use std::mem;
 
 pub trait Iter8or {
@@ -10,6 +13,15 @@ pub trait Iter8or {
         (0, None)
     }
 
+    /// Counts the items by driving the iterator to exhaustion.
+    ///
+    /// ```
+    /// use iterators::Iter8or;
+    /// use iterators::words_from_bufread::{Words, is_word_char};
+    ///
+    /// let words = Words::new("one two\nthree".as_bytes(), is_word_char);
+    /// assert_eq!( words.count(), 3 );
+    /// ```
     fn count(mut self)-> usize
         where Self: Sized
     {
@@ -22,6 +34,36 @@ pub trait Iter8or {
         result
     }
 
+    /// Combines the items into one value, starting from `init` and
+    /// applying `fun` to the accumulator and each item in turn.
+    ///
+    /// ```
+    /// use iterators::{Iter8or, IntoIter8or};
+    ///
+    /// let sum = vec![1, 2, 3, 4].into_iter8or().fold(0, |acc, x| acc + x);
+    /// assert_eq!( sum, 10 );
+    /// ```
+    fn fold<B, F: FnMut(B, Self::Item) -> B>(mut self, init: B, mut fun: F) -> B
+        where Self: Sized
+    {
+        let mut result = init;
+
+        while let Some(item) = self.next() {
+            result = fun(result, item);
+        }
+
+        result
+    }
+
+    /// Calls `fun` on each item, driving the iterator to exhaustion.
+    fn for_each<F: FnMut(Self::Item)>(mut self, mut fun: F)
+        where Self: Sized
+    {
+        while let Some(item) = self.next() {
+            fun(item);
+        }
+    }
+
     fn last(mut self) -> Option<Self::Item>
         where Self: Sized
     {
@@ -34,9 +76,10 @@ pub trait Iter8or {
         result
     }
 
-    fn nth(mut self, mut n: usize) -> Option<Self::Item>
-        where Self: Sized
-    {
+    /// Returns the `n`th item, counting from 0, consuming it and every
+    /// item before it. Returns `None` if there are `n` or fewer items
+    /// left.
+    fn nth(&mut self, mut n: usize) -> Option<Self::Item> {
         while n > 0 {
             if self.next().is_none() { return None; }
             n -= 1;
@@ -45,6 +88,114 @@ pub trait Iter8or {
         self.next()
     }
 
+    /// Returns the first item satisfying `pred`, consuming it and every
+    /// item before it.
+    fn find<P: FnMut(&Self::Item) -> bool>(&mut self, mut pred: P) -> Option<Self::Item> {
+        while let Some(item) = self.next() {
+            if pred(&item) {
+                return Some(item);
+            }
+        }
+
+        None
+    }
+
+    fn take(self, n: usize) -> Take<Self>
+        where Self: Sized
+    {
+        Take { base: self, n }
+    }
+
+    fn skip(self, n: usize) -> Skip<Self>
+        where Self: Sized
+    {
+        Skip { base: self, n }
+    }
+
+    fn take_while<P: FnMut(&Self::Item) -> bool>(self, pred: P) -> TakeWhile<Self, P>
+        where Self: Sized
+    {
+        TakeWhile { base: self, pred, done: false }
+    }
+
+    fn skip_while<P: FnMut(&Self::Item) -> bool>(self, pred: P) -> SkipWhile<Self, P>
+        where Self: Sized
+    {
+        SkipWhile { base: self, pred, done: false }
+    }
+
+    /// Threads `state` through `fun`, which produces each item from the
+    /// state and a base item. Iteration ends the first time `fun`
+    /// returns `None`.
+    fn scan<St, B, F>(self, state: St, fun: F) -> Scan<Self, St, F>
+        where F: FnMut(&mut St, Self::Item) -> Option<B>,
+              Self: Sized
+    {
+        Scan { base: self, state, fun, done: false }
+    }
+
+    /// Calls `fun` on a reference to each item as it passes through,
+    /// for debugging a chain of adapters.
+    fn inspect<F: FnMut(&Self::Item)>(self, fun: F) -> Inspect<Self, F>
+        where Self: Sized
+    {
+        Inspect { base: self, fun }
+    }
+
+    /// Skips each item equal to the one before it, so runs of equal
+    /// items collapse into one, as in `Vec::dedup`.
+    fn dedup(self) -> Dedup<Self>
+        where Self::Item: PartialEq,
+              Self: Sized
+    {
+        Dedup { base: self, next: None }
+    }
+
+    /// Groups the items into vectors of `n`, except that the last may be
+    /// shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    fn chunks(self, n: usize) -> Chunks<Self>
+        where Self: Sized
+    {
+        assert!(n > 0, "Iter8or::chunks: n must be positive");
+        Chunks { base: self, n }
+    }
+
+    /// Yields each run of `n` consecutive items, overlapping; see `NGrams`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    fn windows(self, n: usize) -> Windows<Self>
+        where Self::Item: Clone,
+              Self: Sized
+    {
+        NGrams::new(self, n)
+    }
+
+    /// Groups runs of consecutive items with equal keys, yielding each
+    /// run with its key. Items with equal keys that aren't adjacent end
+    /// up in separate groups.
+    fn group_by_key<K, F>(self, key: F) -> GroupBy<Self, F, K>
+        where K: PartialEq,
+              F: FnMut(&Self::Item) -> K,
+              Self: Sized
+    {
+        GroupBy { base: self, key, next: None }
+    }
+
+    /// Yields a clone of `sep` between each pair of items, but not
+    /// before the first or after the last.
+    fn intersperse(self, sep: Self::Item) -> Intersperse<Self>
+        where Self::Item: Clone,
+              Self: Sized
+    {
+        Intersperse { base: self.peekable(), sep, need_sep: false }
+    }
+
     fn by_ref(&mut self) -> &mut Self {
         self
     }
@@ -73,10 +224,77 @@ pub trait Iter8or {
         }
     }
 
-    fn any<P: FnMut(Self::Item) -> bool>(self, pred: P) -> bool
+    /// Checks whether every item satisfies `pred`, stopping at the
+    /// first that doesn't.
+    fn all<P: FnMut(Self::Item) -> bool>(&mut self, mut pred: P) -> bool {
+        while let Some(item) = self.next() {
+            if !pred(item) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Checks whether any item satisfies `pred`, stopping at the first
+    /// that does.
+    fn any<P: FnMut(Self::Item) -> bool>(&mut self, mut pred: P) -> bool {
+        while let Some(item) = self.next() {
+            if pred(item) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns the index of the first item satisfying `pred`, stopping
+    /// there.
+    fn position<P: FnMut(Self::Item) -> bool>(&mut self, mut pred: P) -> Option<usize> {
+        let mut index = 0;
+
+        while let Some(item) = self.next() {
+            if pred(item) {
+                return Some(index);
+            }
+            index += 1;
+        }
+
+        None
+    }
+
+    /// Returns the greatest item, or the last of several equally great.
+    fn max(self) -> Option<Self::Item>
+        where Self::Item: Ord,
+              Self: Sized
+    {
+        self.fold(None, |best, item| match best {
+            Some(best) if best > item => Some(best),
+            _ => Some(item),
+        })
+    }
+
+    /// Returns the least item, or the first of several equally least.
+    fn min(self) -> Option<Self::Item>
+        where Self::Item: Ord,
+              Self: Sized
+    {
+        self.fold(None, |best, item| match best {
+            Some(best) if best <= item => Some(best),
+            _ => Some(item),
+        })
+    }
+
+    fn sum<S: Sum<Self::Item>>(self) -> S
         where Self: Sized
     {
-        self.map(pred).filter(|&b| b).next().is_some()
+        S::sum(self.into_std())
+    }
+
+    fn product<P: Product<Self::Item>>(self) -> P
+        where Self: Sized
+    {
+        P::product(self.into_std())
     }
 
     fn max_by_key<B, F>(mut self, mut get_key: F) -> Option<Self::Item>
@@ -140,6 +358,14 @@ pub trait Iter8or {
         }
     }
 
+    /// Adapts this into a `std::iter::Iterator`, for using the standard
+    /// library's adapters and consumers.
+    fn into_std(self) -> IntoStd<Self>
+        where Self: Sized
+    {
+        IntoStd(self)
+    }
+
     fn peekable(self) -> Peek<Self>
         where Self: Sized
     {
@@ -299,6 +525,381 @@ impl<I> ExactSizeIter8or for Enumerate<I>
     }
 }
 
+pub struct Take<I> {
+    base: I,
+    n:    usize,
+}
+
+impl<I: Iter8or> Iter8or for Take<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.n == 0 {
+            None
+        } else {
+            self.n -= 1;
+            self.base.next()
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper_option) = self.base.size_hint();
+
+        let lower = cmp::min(lower, self.n);
+        let upper = match upper_option {
+            Some(upper) => cmp::min(upper, self.n),
+            None => self.n,
+        };
+
+        (lower, Some(upper))
+    }
+}
+
+impl<I: ExactSizeIter8or> ExactSizeIter8or for Take<I> {
+    fn len(&self) -> usize {
+        cmp::min(self.base.len(), self.n)
+    }
+}
+
+/// Skips `n` items the first time it's advanced, then passes the rest
+/// through.
+pub struct Skip<I> {
+    base: I,
+    n:    usize,
+}
+
+impl<I: Iter8or> Iter8or for Skip<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        while self.n > 0 {
+            self.n -= 1;
+            self.base.next()?;
+        }
+
+        self.base.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper_option) = self.base.size_hint();
+
+        (lower.saturating_sub(self.n),
+         upper_option.map(|upper| upper.saturating_sub(self.n)))
+    }
+}
+
+impl<I: ExactSizeIter8or> ExactSizeIter8or for Skip<I> {
+    fn len(&self) -> usize {
+        self.base.len().saturating_sub(self.n)
+    }
+}
+
+/// Passes items through until the first that fails `pred`, and then
+/// stops for good, without pulling any more from the base.
+pub struct TakeWhile<I, P> {
+    base: I,
+    pred: P,
+    done: bool,
+}
+
+impl<I, P> Iter8or for TakeWhile<I, P>
+    where I: Iter8or,
+          P: FnMut(&I::Item) -> bool
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.base.next() {
+            Some(item) => if (self.pred)(&item) {
+                Some(item)
+            } else {
+                self.done = true;
+                None
+            },
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.base.size_hint().1)
+        }
+    }
+}
+
+/// Skips the leading run of items that satisfy `pred`, then passes the
+/// rest through without checking them.
+pub struct SkipWhile<I, P> {
+    base: I,
+    pred: P,
+    done: bool,
+}
+
+impl<I, P> Iter8or for SkipWhile<I, P>
+    where I: Iter8or,
+          P: FnMut(&I::Item) -> bool
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.done {
+            return self.base.next();
+        }
+
+        while let Some(item) = self.base.next() {
+            if !(self.pred)(&item) {
+                self.done = true;
+                return Some(item);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper_option) = self.base.size_hint();
+
+        if self.done {
+            (lower, upper_option)
+        } else {
+            (0, upper_option)
+        }
+    }
+}
+
+pub struct Scan<I, St, F> {
+    base:  I,
+    state: St,
+    fun:   F,
+    done:  bool,
+}
+
+impl<I, St, F, B> Iter8or for Scan<I, St, F>
+    where I: Iter8or,
+          F: FnMut(&mut St, I::Item) -> Option<B>
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<B> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.base.next().and_then(|item| (self.fun)(&mut self.state, item));
+        self.done = result.is_none();
+        result
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.base.size_hint().1)
+        }
+    }
+}
+
+pub struct Inspect<I, F> {
+    base: I,
+    fun:  F,
+}
+
+impl<I, F> Iter8or for Inspect<I, F>
+    where I: Iter8or,
+          F: FnMut(&I::Item)
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.base.next();
+
+        if let Some(ref item) = item {
+            (self.fun)(item);
+        }
+
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+}
+
+impl<I, F> ExactSizeIter8or for Inspect<I, F>
+    where I: ExactSizeIter8or,
+          F: FnMut(&I::Item)
+{
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+pub struct Dedup<I: Iter8or> {
+    base: I,
+    // To know when a run ends we have to read one item past it, so that
+    // item waits here.
+    next: Option<I::Item>,
+}
+
+impl<I> Iter8or for Dedup<I>
+    where I: Iter8or,
+          I::Item: PartialEq
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let current = self.next.take().or_else(|| self.base.next())?;
+
+        loop {
+            match self.base.next() {
+                Some(item) => if item != current {
+                    self.next = Some(item);
+                    return Some(current);
+                },
+                None => return Some(current),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = if self.next.is_some() {1} else {0};
+        let (lower, upper_option) = self.base.size_hint();
+
+        (cmp::min(lower.saturating_add(extra), 1),
+         upper_option.and_then(|upper| upper.checked_add(extra)))
+    }
+}
+
+pub struct Chunks<I> {
+    base: I,
+    n:    usize,
+}
+
+impl<I: Iter8or> Iter8or for Chunks<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        let chunk: Vec<I::Item> = self.base.by_ref().take(self.n).collect();
+
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(chunk)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper_option) = self.base.size_hint();
+        let chunks = |len: usize| len.div_ceil(self.n);
+
+        (chunks(lower), upper_option.map(chunks))
+    }
+}
+
+pub type Windows<I> = NGrams<I>;
+
+pub struct GroupBy<I: Iter8or, F, K> {
+    base: I,
+    key:  F,
+    // The first item of the next group, which we had to read to know
+    // the previous group ended.
+    next: Option<(K, I::Item)>,
+}
+
+impl<I, F, K> Iter8or for GroupBy<I, F, K>
+    where I: Iter8or,
+          K: PartialEq,
+          F: FnMut(&I::Item) -> K
+{
+    type Item = (K, Vec<I::Item>);
+
+    fn next(&mut self) -> Option<(K, Vec<I::Item>)> {
+        let (key, first) = match self.next.take() {
+            Some(next) => next,
+            None => {
+                let item = self.base.next()?;
+                ((self.key)(&item), item)
+            }
+        };
+
+        let mut group = vec![first];
+
+        while let Some(item) = self.base.next() {
+            let item_key = (self.key)(&item);
+            if item_key == key {
+                group.push(item);
+            } else {
+                self.next = Some((item_key, item));
+                break;
+            }
+        }
+
+        Some((key, group))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = if self.next.is_some() {1} else {0};
+        let (lower, upper_option) = self.base.size_hint();
+
+        (cmp::min(lower.saturating_add(extra), 1),
+         upper_option.and_then(|upper| upper.checked_add(extra)))
+    }
+}
+
+pub struct Intersperse<I: Iter8or> {
+    base:     Peek<I>,
+    sep:      I::Item,
+    need_sep: bool,
+}
+
+impl<I> Iter8or for Intersperse<I>
+    where I: Iter8or,
+          I::Item: Clone
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if self.need_sep && self.base.peek().is_some() {
+            self.need_sep = false;
+            Some(self.sep.clone())
+        } else {
+            let item = self.base.next()?;
+            self.need_sep = true;
+            Some(item)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each item but the first brings a separator with it, plus one
+        // more if we owe a separator before the next item.
+        // The lower bound saturates, and an upper bound too big for a
+        // `usize` becomes no bound at all.
+        let owed = if self.need_sep {1} else {0};
+        let (lower, upper_option) = self.base.size_hint();
+
+        let lower = if lower == 0 {
+            0
+        } else {
+            lower.checked_mul(2).map_or(usize::MAX, |n| n - 1 + owed)
+        };
+        let upper_option = upper_option.and_then(|upper| {
+            if upper == 0 {
+                Some(0)
+            } else {
+                upper.checked_mul(2).and_then(|n| (n - 1).checked_add(owed))
+            }
+        });
+
+        (lower, upper_option)
+    }
+}
+
 pub struct Chain<A, B>(ChainImpl<A, B>);
 
 enum ChainImpl<A, B> {
@@ -481,7 +1082,8 @@ impl <I, F, U> Iter8or for FlatMap<I, F, U>
                 Some(mut inner) => {
                     match inner.next() {
                         None => {
-                            self.buf = Some(inner);
+                            // This inner iterator is done, so leave
+                            // `buf` empty to move on to the next one.
                             continue;
                         }
                         Some(item) => {
@@ -532,6 +1134,52 @@ impl<I: ExactSizeIter8or> ExactSizeIter8or for Peek<I> {
     }
 }
 
+pub struct IntoStd<I>(I);
+
+impl<I: Iter8or> Iterator for IntoStd<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Adapts a `std::iter::Iterator` into an `Iter8or`, the reverse of
+/// `Iter8or::into_std`.
+pub fn from_std<I: Iterator>(iter: I) -> FromStd<I> {
+    FromStd(iter)
+}
+
+pub struct FromStd<I>(I);
+
+impl<I: Iterator> Iter8or for FromStd<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIter8or for FromStd<I> {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIter8or for FromStd<I> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.0.next_back()
+    }
+}
+
 impl<'a, T: Iter8or> Iter8or for &'a mut T {
     type Item = T::Item;
 
@@ -544,6 +1192,32 @@ impl<'a, T: Iter8or> Iter8or for &'a mut T {
     }
 }
 
+impl FromIter8or<char> for String {
+    fn from_iter<I: IntoIter8or<Item=char>>(iter: I) -> Self {
+        let mut iter = iter.into_iter8or();
+        let mut result = String::with_capacity(iter.size_hint().0);
+
+        while let Some(c) = iter.next() {
+            result.push(c);
+        }
+
+        result
+    }
+}
+
+impl<'a> FromIter8or<&'a str> for String {
+    fn from_iter<I: IntoIter8or<Item=&'a str>>(iter: I) -> Self {
+        let mut iter = iter.into_iter8or();
+        let mut result = String::new();
+
+        while let Some(s) = iter.next() {
+            result.push_str(s);
+        }
+
+        result
+    }
+}
+
 impl<T, E, C> FromIter8or<Result<T, E>> for Result<C, E>
     where C: FromIter8or<T>
 {
@@ -581,3 +1255,450 @@ impl<T, E, C> FromIter8or<Result<T, E>> for Result<C, E>
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_doubles() {
+        let actual: Vec<i32> = vec![1, 2, 3].into_iter8or().map(|x| x * 2).collect();
+        assert_eq!( actual, vec![2, 4, 6] );
+    }
+
+    #[test]
+    fn filter_drops_odd_numbers() {
+        let actual: Vec<i32> = vec![1, 2, 3, 4, 5].into_iter8or()
+            .filter(|x| x % 2 == 0)
+            .collect();
+        assert_eq!( actual, vec![2, 4] );
+    }
+
+    #[test]
+    fn filter_words_by_length() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: Vec<String> =
+            Words::new("a fine day to go out".as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .filter(|word| word.len() > 2)
+                .collect();
+        assert_eq!( actual, vec!["fine", "day", "out"] );
+    }
+
+    #[test]
+    fn take_words() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let take = |n| -> Vec<String> {
+            Words::new("one two three four".as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .take(n)
+                .collect()
+        };
+        assert_eq!( take(0), Vec::<String>::new() );
+        assert_eq!( take(3), vec!["one", "two", "three"] );
+        assert_eq!( take(4), vec!["one", "two", "three", "four"] );
+        assert_eq!( take(10), vec!["one", "two", "three", "four"] );
+    }
+
+    #[test]
+    fn skip_words() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let skip = |n| -> Vec<String> {
+            Words::new("one two three four".as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .skip(n)
+                .collect()
+        };
+        assert_eq!( skip(0), vec!["one", "two", "three", "four"] );
+        assert_eq!( skip(2), vec!["three", "four"] );
+        assert_eq!( skip(4), Vec::<String>::new() );
+        assert_eq!( skip(10), Vec::<String>::new() );
+    }
+
+    #[test]
+    fn take_and_skip_size_hints() {
+        let v = vec![1, 2, 3, 4, 5];
+        assert_eq!( v.clone().into_iter8or().take(3).size_hint(), (3, Some(3)) );
+        assert_eq!( v.clone().into_iter8or().take(9).len(), 5 );
+        assert_eq!( v.clone().into_iter8or().skip(2).size_hint(), (3, Some(3)) );
+        assert_eq!( v.into_iter8or().skip(9).len(), 0 );
+    }
+
+    #[test]
+    fn enumerate_numbers_words() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: Vec<(usize, String)> =
+            Words::new("alpha beta gamma".as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .enumerate()
+                .collect();
+        assert_eq!( actual, vec![(0, "alpha".to_owned()),
+                                 (1, "beta".to_owned()),
+                                 (2, "gamma".to_owned())] );
+    }
+
+    #[test]
+    fn zip_stops_at_shorter() {
+        let short = vec![1, 2];
+        let long  = vec!["a", "b", "c"];
+
+        let actual: Vec<(i32, &str)> =
+            short.clone().into_iter8or().zip(long.clone()).collect();
+        assert_eq!( actual, vec![(1, "a"), (2, "b")] );
+
+        let actual: Vec<(&str, i32)> = long.into_iter8or().zip(short).collect();
+        assert_eq!( actual, vec![("a", 1), ("b", 2)] );
+    }
+
+    #[test]
+    fn chain_words_from_two_inputs() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let first  = Words::new("one two".as_bytes(), is_word_char);
+        let second = Words::new("three\nfour".as_bytes(), is_word_char);
+
+        let actual: Vec<String> = first.chain(second).map(Result::unwrap).collect();
+        assert_eq!( actual, vec!["one", "two", "three", "four"] );
+    }
+
+    #[test]
+    fn collect_words_into_vec() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: Vec<String> = Words::new("to be".as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!( actual, vec!["to".to_owned(), "be".to_owned()] );
+
+        let result: Result<Vec<String>, _> =
+            Words::new("or not".as_bytes(), is_word_char).collect();
+        assert_eq!( result.unwrap(), vec!["or".to_owned(), "not".to_owned()] );
+    }
+
+    #[test]
+    fn collect_into_string() {
+        let chars: String = vec!['a', 'b', 'c'].into_iter8or().collect();
+        assert_eq!( chars, "abc" );
+
+        let strs: String = vec!["ab", "", "cd"].into_iter8or().collect();
+        assert_eq!( strs, "abcd" );
+    }
+
+    #[test]
+    fn into_std_bridges_to_std_adapters() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let long_words = Words::new("a fine day to go out".as_bytes(), is_word_char)
+            .into_std()
+            .map(Result::unwrap)
+            .filter(|word| word.len() > 2)
+            .count();
+        assert_eq!( long_words, 3 );
+    }
+
+    #[test]
+    fn from_std_feeds_iter8or_adapters() {
+        let actual: Vec<i32> = from_std(0 .. 5).map(|x| x * x).collect();
+        assert_eq!( actual, vec![0, 1, 4, 9, 16] );
+        assert_eq!( from_std(0 .. 5).len(), 5 );
+    }
+
+    #[test]
+    fn for_each_visits_each_item_once() {
+        let mut seen = Vec::new();
+        vec![1, 2, 3].into_iter8or().for_each(|x| seen.push(x));
+        assert_eq!( seen, vec![1, 2, 3] );
+    }
+
+    #[test]
+    fn nth_consumes_skipped_items() {
+        let mut iter = vec![1, 2, 3, 4].into_iter8or();
+        assert_eq!( iter.nth(1), Some(2) );
+        assert_eq!( iter.nth(0), Some(3) );
+        assert_eq!( iter.nth(1), None );
+        assert_eq!( iter.next(), None );
+    }
+
+    #[test]
+    fn last_drives_to_exhaustion() {
+        assert_eq!( vec![1, 2, 3].into_iter8or().last(), Some(3) );
+        assert_eq!( Vec::<i32>::new().into_iter8or().last(), None );
+    }
+
+    #[test]
+    fn find_stops_at_first_match() {
+        let mut iter = vec![1, 2, 3, 4].into_iter8or();
+        assert_eq!( iter.find(|&x| x % 2 == 0), Some(2) );
+        assert_eq!( iter.next(), Some(3) );
+        assert_eq!( iter.find(|&x| x > 10), None );
+        assert_eq!( iter.next(), None );
+    }
+
+    #[test]
+    fn all_any_and_position_short_circuit() {
+        let v = vec![1, 2, 3, 4, 5];
+        let mut calls = 0;
+
+        assert!( v.clone().into_iter8or().any(|x| { calls += 1; x == 2 }) );
+        assert_eq!( calls, 2 );
+
+        calls = 0;
+        assert!( !v.clone().into_iter8or().all(|x| { calls += 1; x < 3 }) );
+        assert_eq!( calls, 3 );
+
+        let mut iter = v.into_iter8or();
+        assert_eq!( iter.position(|x| x == 4), Some(3) );
+        assert_eq!( iter.next(), Some(5) );
+        assert_eq!( iter.position(|_| true), None );
+
+        assert!( Vec::<i32>::new().into_iter8or().all(|_| false) );
+        assert!( !Vec::<i32>::new().into_iter8or().any(|_| true) );
+    }
+
+    #[test]
+    fn aggregates() {
+        let v = vec![3, 1, 4, 1, 5];
+
+        assert_eq!( v.clone().into_iter8or().max(), Some(5) );
+        assert_eq!( v.clone().into_iter8or().min(), Some(1) );
+        assert_eq!( v.clone().into_iter8or().sum::<i32>(), 14 );
+        assert_eq!( v.into_iter8or().product::<i32>(), 60 );
+
+        assert_eq!( Vec::<i32>::new().into_iter8or().max(), None );
+        assert_eq!( Vec::<i32>::new().into_iter8or().sum::<i32>(), 0 );
+        assert_eq!( Vec::<i32>::new().into_iter8or().product::<i32>(), 1 );
+    }
+
+    #[test]
+    fn max_and_min_break_ties_like_std() {
+        let v = vec![(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+        let by_key = |pairs: &Vec<(i32, char)>| -> Vec<Key> {
+            pairs.iter().map(|&(k, c)| Key(k, c)).collect()
+        };
+
+        assert_eq!( by_key(&v).into_iter8or().max().map(|k| k.1), Some('c') );
+        assert_eq!( by_key(&v).into_iter8or().min().map(|k| k.1), Some('b') );
+    }
+
+    /// Ordered by the first field only.
+    #[derive(Debug)]
+    struct Key(i32, char);
+
+    impl PartialEq for Key {
+        fn eq(&self, other: &Key) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Key {}
+
+    impl PartialOrd for Key {
+        fn partial_cmp(&self, other: &Key) -> Option<::std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Key {
+        fn cmp(&self, other: &Key) -> ::std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    #[test]
+    fn filter_map_parses_ints() {
+        let actual: Vec<i32> = vec!["1", "two", "3", "", "-4"].into_iter8or()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        assert_eq!( actual, vec![1, 3, -4] );
+    }
+
+    #[test]
+    fn flat_map_expands_words_into_chars() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: String = Words::new("ab, c de".as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .flat_map(|word| from_std(word.chars().collect::<Vec<_>>().into_iter()))
+            .collect();
+        assert_eq!( actual, "abcde" );
+
+        let flattened: Vec<i32> = vec![vec![], vec![1], vec![], vec![2, 3]].into_iter8or()
+            .flat_map(|v| v)
+            .collect();
+        assert_eq!( flattened, vec![1, 2, 3] );
+    }
+
+    #[test]
+    fn take_while_stops_at_stop_word() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let mut words = Words::new("go on STOP here go".as_bytes(), is_word_char)
+            .map(Result::unwrap);
+
+        let before: Vec<String> = words.by_ref().take_while(|w| w != "STOP").collect();
+        assert_eq!( before, vec!["go", "on"] );
+        // The stop word itself is consumed, but nothing after it.
+        assert_eq!( words.next(), Some("here".to_owned()) );
+    }
+
+    #[test]
+    fn take_while_pulls_nothing_after_stopping() {
+        let mut pulled = 0;
+        let mut iter = vec![1, 2, 5, 3, 4].into_iter8or()
+            .map(|x| { pulled += 1; x })
+            .take_while(|&x| x < 5);
+
+        assert_eq!( iter.next(), Some(1) );
+        assert_eq!( iter.next(), Some(2) );
+        assert_eq!( iter.next(), None );
+        assert_eq!( iter.next(), None );
+        drop(iter);
+        assert_eq!( pulled, 3 );
+    }
+
+    #[test]
+    fn skip_while_skips_only_leading_run() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let after: Vec<String> = Words::new("um um so um yes".as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .skip_while(|w| w == "um")
+            .collect();
+        assert_eq!( after, vec!["so", "um", "yes"] );
+    }
+
+    #[test]
+    fn scan_accumulates_until_cap() {
+        use words_from_bufread::{Words, is_word_char};
+
+        const CAP: usize = 12;
+
+        let totals: Vec<usize> = Words::new("the quick brown fox jumps".as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .scan(0, |total, word| {
+                *total += word.len();
+                if *total <= CAP { Some(*total) } else { None }
+            })
+            .collect();
+        assert_eq!( totals, vec![3, 8] );
+    }
+
+    #[test]
+    fn inspect_sees_every_item_in_order() {
+        let mut seen = Vec::new();
+
+        let actual: Vec<i32> = vec![1, 2, 3].into_iter8or()
+            .inspect(|&x| seen.push(x))
+            .map(|x| x * 10)
+            .collect();
+
+        assert_eq!( actual, vec![10, 20, 30] );
+        assert_eq!( seen, vec![1, 2, 3] );
+    }
+
+    #[test]
+    fn dedup_collapses_only_consecutive_runs() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: Vec<String> = Words::new("a a b a".as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .dedup()
+            .collect();
+        assert_eq!( actual, vec!["a", "b", "a"] );
+
+        let actual: Vec<i32> = vec![1, 1, 1, 2, 2, 3, 1, 1].into_iter8or().dedup().collect();
+        assert_eq!( actual, vec![1, 2, 3, 1] );
+
+        assert_eq!( Vec::<i32>::new().into_iter8or().dedup().next(), None );
+    }
+
+    #[test]
+    fn dedup_size_hint_does_not_overflow() {
+        let mut iter = from_std(0u64 ..).dedup();
+        assert_eq!( iter.size_hint(), (1, None) );
+        assert_eq!( iter.next(), Some(0) );
+        assert_eq!( iter.size_hint(), (1, None) );
+    }
+
+    #[test]
+    fn chunks_keep_short_last_chunk() {
+        let actual: Vec<Vec<i32>> = vec![1, 2, 3, 4, 5].into_iter8or().chunks(2).collect();
+        assert_eq!( actual, vec![vec![1, 2], vec![3, 4], vec![5]] );
+
+        let actual: Vec<Vec<i32>> = vec![1, 2, 3, 4].into_iter8or().chunks(2).collect();
+        assert_eq!( actual, vec![vec![1, 2], vec![3, 4]] );
+
+        assert_eq!( vec![1, 2, 3].into_iter8or().chunks(2).size_hint(), (2, Some(2)) );
+        assert_eq!( Vec::<i32>::new().into_iter8or().chunks(2).next(), None );
+    }
+
+    #[test]
+    fn windows_need_n_items() {
+        let actual: Vec<Vec<i32>> = vec![1, 2, 3, 4].into_iter8or().windows(3).collect();
+        assert_eq!( actual, vec![vec![1, 2, 3], vec![2, 3, 4]] );
+
+        assert_eq!( vec![1, 2].into_iter8or().windows(3).next(), None );
+    }
+
+    #[test]
+    fn group_by_first_letter() {
+        use words_from_bufread::{Words, is_word_char};
+
+        let actual: Vec<(char, Vec<String>)> =
+            Words::new("apple ant bee axe".as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .group_by_key(|word| word.chars().next().unwrap())
+                .collect();
+        let expected = vec![
+            ('a', vec!["apple".to_owned(), "ant".to_owned()]),
+            ('b', vec!["bee".to_owned()]),
+            ('a', vec!["axe".to_owned()]),
+        ];
+        assert_eq!( actual, expected );
+
+        assert_eq!( Vec::<i32>::new().into_iter8or().group_by_key(|&x| x).next(), None );
+    }
+
+    #[test]
+    fn group_by_size_hint_does_not_overflow() {
+        let mut iter = from_std(0u64 ..).group_by_key(|&n| n / 2);
+        assert_eq!( iter.size_hint(), (1, None) );
+        assert_eq!( iter.next(), Some((0, vec![0, 1])) );
+        assert_eq!( iter.size_hint(), (1, None) );
+    }
+
+    #[test]
+    fn intersperse_only_between_items() {
+        let intersperse = |v: Vec<&'static str>| -> Vec<&'static str> {
+            v.into_iter8or().intersperse(" ").collect()
+        };
+
+        assert_eq!( intersperse(vec!["a", "b", "c"]), vec!["a", " ", "b", " ", "c"] );
+        assert_eq!( intersperse(vec!["a"]), vec!["a"] );
+        assert_eq!( intersperse(vec![]), Vec::<&str>::new() );
+
+        let mut iter = vec![1, 2, 3].into_iter8or().intersperse(0);
+        assert_eq!( iter.size_hint(), (5, Some(5)) );
+        iter.next();
+        assert_eq!( iter.size_hint(), (4, Some(4)) );
+        iter.next();
+        assert_eq!( iter.size_hint(), (3, Some(3)) );
+    }
+
+    #[test]
+    fn intersperse_size_hint_does_not_overflow() {
+        let iter = from_std(0u32 ..).intersperse(9);
+        assert_eq!( iter.size_hint(), (usize::MAX, None) );
+        let actual: Vec<u32> = from_std(0u32 ..).intersperse(9).take(5).collect();
+        assert_eq!( actual, vec![0, 9, 1, 9, 2] );
+
+        let mut iter = from_std(0 .. usize::MAX).intersperse(0);
+        assert_eq!( iter.size_hint(), (usize::MAX, None) );
+        iter.next();
+        assert_eq!( iter.size_hint(), (usize::MAX, None) );
+    }
+}
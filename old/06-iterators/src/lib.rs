@@ -6,6 +6,11 @@ pub mod iter8or;
 pub mod vec;
 pub mod words_from_chars;
 pub mod words_from_bufread;
+pub mod words_from_str;
+pub mod words_from_read;
+pub mod ngrams;
+pub mod sentences;
+pub mod word_char_set;
 pub mod list_set;
 
 pub use iter8or::*;
@@ -0,0 +1,97 @@
+use super::{Iter8or, IntoIter8or};
+
+use std::collections::VecDeque;
+
+/// Sliding windows of `n` consecutive items, such as the n-grams of a
+/// stream of words. Each window overlaps the last in all but one item,
+/// and an input shorter than `n` yields no windows at all.
+///
+/// Only the current window is buffered.
+pub struct NGrams<I: Iter8or> {
+    base:   I,
+    n:      usize,
+    window: VecDeque<I::Item>,
+}
+
+impl<I: Iter8or> NGrams<I> where I::Item: Clone {
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn new<J: IntoIter8or<IntoIter = I>>(base: J, n: usize) -> Self {
+        assert!(n > 0, "NGrams::new: n must be positive");
+
+        NGrams {
+            base:   base.into_iter8or(),
+            n,
+            window: VecDeque::with_capacity(n),
+        }
+    }
+}
+
+impl<I: Iter8or> Iter8or for NGrams<I> where I::Item: Clone {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Vec<I::Item>> {
+        if self.window.len() == self.n {
+            self.window.pop_front();
+        }
+
+        while self.window.len() < self.n {
+            self.window.push_back(self.base.next()?);
+        }
+
+        Some(self.window.iter().cloned().collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Each further item completes one more window, except that until
+        // the first window is full, the first `n - 1` items don't count.
+        let (lower, upper_option) = self.base.size_hint();
+        let needed = self.n - 1 - self.window.len().min(self.n - 1);
+
+        (lower.saturating_sub(needed),
+         upper_option.map(|upper| upper.saturating_sub(needed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NGrams;
+    use super::super::Iter8or;
+    use super::super::words_from_bufread::{Words, is_word_char};
+
+    #[test]
+    fn bigrams() {
+        assert_ngrams("a b c d", 2, &[&["a", "b"], &["b", "c"], &["c", "d"]]);
+    }
+
+    #[test]
+    fn unigrams_and_whole_input() {
+        assert_ngrams("a b c", 1, &[&["a"], &["b"], &["c"]]);
+        assert_ngrams("a b c", 3, &[&["a", "b", "c"]]);
+    }
+
+    #[test]
+    fn too_short() {
+        assert_ngrams("a b", 3, &[]);
+        assert_ngrams("", 1, &[]);
+    }
+
+    #[test]
+    fn size_hint() {
+        let mut ngrams = NGrams::new(vec![1, 2, 3, 4], 2);
+        assert_eq!( ngrams.size_hint(), (3, Some(3)) );
+        ngrams.next();
+        assert_eq!( ngrams.size_hint(), (2, Some(2)) );
+        assert_eq!( NGrams::new(vec![1], 2).size_hint(), (0, Some(0)) );
+    }
+
+    fn assert_ngrams(input: &str, n: usize, expected: &[&[&str]]) {
+        let words = Words::new(input.as_bytes(), is_word_char).map(Result::unwrap);
+        let actual: Vec<Vec<String>> = NGrams::new(words, n).collect();
+        let expected: Vec<Vec<String>> = expected.iter()
+            .map(|gram| gram.iter().map(|&s| s.to_owned()).collect())
+            .collect();
+        assert_eq!( actual, expected );
+    }
+}
@@ -0,0 +1,128 @@
+use super::{Iter8or, IntoIter8or, vec::VecIntoIter};
+
+use std::io;
+
+/// Like `Words`, but yields sentences. A sentence ends at each `.`, `!`,
+/// or `?`, which is dropped, and is trimmed of surrounding whitespace.
+/// Sentences may span lines, which are joined with a space. Runs of
+/// terminators, as in `"?!"`, produce no empty sentences, and whatever
+/// follows the last terminator is a final sentence if it isn't blank.
+///
+/// This doesn't try to recognize abbreviations, so `"Dr. Who"` is two
+/// sentences.
+pub struct Sentences<R> {
+    lines:     io::Lines<R>,
+    pending:   String,
+    sentences: VecIntoIter<String>,
+    done:      bool,
+}
+
+impl<R: io::BufRead> Sentences<R> {
+    pub fn new(input: R) -> Self {
+        Sentences {
+            lines:     input.lines(),
+            pending:   String::new(),
+            sentences: Vec::new().into_iter8or(),
+            done:      false,
+        }
+    }
+
+    /// Moves the complete sentences out of `pending`, leaving only the
+    /// unterminated tail.
+    fn split_pending(&mut self) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut start  = 0;
+
+        for (i, c) in self.pending.char_indices() {
+            if is_terminator(c) {
+                push_trimmed(&mut result, &self.pending[start .. i]);
+                start = i + c.len_utf8();
+            }
+        }
+
+        self.pending.drain(.. start);
+        result
+    }
+}
+
+impl<R: io::BufRead> Iter8or for Sentences<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        loop {
+            if let Some(sentence) = self.sentences.next() {
+                return Some(Ok(sentence));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            match self.lines.next() {
+                Some(Ok(line)) => {
+                    if !self.pending.is_empty() {
+                        self.pending.push(' ');
+                    }
+                    self.pending.push_str(&line);
+                    self.sentences = self.split_pending().into_iter8or();
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.done = true;
+                    let mut last = Vec::new();
+                    push_trimmed(&mut last, &self.pending);
+                    self.pending.clear();
+                    self.sentences = last.into_iter8or();
+                }
+            }
+        }
+    }
+}
+
+fn is_terminator(c: char) -> bool {
+    c == '.' || c == '!' || c == '?'
+}
+
+fn push_trimmed(sentences: &mut Vec<String>, sentence: &str) {
+    let sentence = sentence.trim();
+
+    if !sentence.is_empty() {
+        sentences.push(sentence.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn two_sentences() {
+        assert_sentences("Hi there. How are you?!", &["Hi there", "How are you"]);
+    }
+
+    #[test]
+    fn unterminated_and_trailing_whitespace() {
+        assert_sentences("One. Two  ", &["One", "Two"]);
+        assert_sentences("One.   \n  ", &["One"]);
+    }
+
+    #[test]
+    fn across_lines() {
+        assert_sentences("This sentence\nspans lines. Done!\n",
+                         &["This sentence spans lines", "Done"]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_sentences("", &[]);
+        assert_sentences("  \n ", &[]);
+        assert_sentences("...!?", &[]);
+    }
+
+    fn assert_sentences(input: &str, expected: &[&str]) {
+        use super::super::Iter8or;
+        use super::Sentences;
+
+        let actual: Vec<String> =
+            Sentences::new(input.as_bytes()).map(Result::unwrap).collect();
+        assert_eq!( actual, expected );
+    }
+}
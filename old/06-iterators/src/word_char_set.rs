@@ -0,0 +1,123 @@
+/// Which characters count as part of a word, built up from common
+/// classes. `WordCharSet::default()` matches `is_word_char`: letters,
+/// digits, and apostrophes. For example, to treat hyphenated words as
+/// single words:
+///
+/// ```
+/// use iterators::Iter8or;
+/// use iterators::word_char_set::WordCharSet;
+/// use iterators::words_from_bufread::Words;
+///
+/// let pred = WordCharSet::default().hyphens(true).into_fn();
+/// let words: Vec<String> = Words::new("a well-known fact".as_bytes(), pred)
+///     .map(Result::unwrap)
+///     .collect();
+/// assert_eq!( words, &["a", "well-known", "fact"] );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordCharSet {
+    letters:     bool,
+    digits:      bool,
+    apostrophes: bool,
+    hyphens:     bool,
+    underscores: bool,
+}
+
+impl WordCharSet {
+    /// Returns a set with no characters in it, to add classes to.
+    pub fn empty() -> Self {
+        WordCharSet {
+            letters:     false,
+            digits:      false,
+            apostrophes: false,
+            hyphens:     false,
+            underscores: false,
+        }
+    }
+
+    /// Alphabetic characters, in the Unicode sense.
+    pub fn letters(self, letters: bool) -> Self {
+        WordCharSet { letters, ..self }
+    }
+
+    /// Numeric characters, in the Unicode sense.
+    pub fn digits(self, digits: bool) -> Self {
+        WordCharSet { digits, ..self }
+    }
+
+    /// `'` and `’`.
+    pub fn apostrophes(self, apostrophes: bool) -> Self {
+        WordCharSet { apostrophes, ..self }
+    }
+
+    /// `-` and `‐` (U+2010, the Unicode hyphen).
+    pub fn hyphens(self, hyphens: bool) -> Self {
+        WordCharSet { hyphens, ..self }
+    }
+
+    /// `_`.
+    pub fn underscores(self, underscores: bool) -> Self {
+        WordCharSet { underscores, ..self }
+    }
+
+    /// Checks whether `c` is in any of the chosen classes.
+    pub fn contains(&self, c: char) -> bool {
+        (self.letters && c.is_alphabetic())
+            || (self.digits && c.is_numeric())
+            || (self.apostrophes && (c == '\'' || c == '’'))
+            || (self.hyphens && (c == '-' || c == '‐'))
+            || (self.underscores && c == '_')
+    }
+
+    /// Converts the set into a predicate to pass to a word iterator.
+    pub fn into_fn(self) -> impl Fn(char) -> bool {
+        move |c| self.contains(c)
+    }
+}
+
+impl Default for WordCharSet {
+    fn default() -> Self {
+        WordCharSet::empty()
+            .letters(true)
+            .digits(true)
+            .apostrophes(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WordCharSet;
+    use super::super::Iter8or;
+    use super::super::words_from_bufread::{Words, is_word_char};
+
+    const INPUT: &str = "it's well-known state_id 3.14";
+
+    #[test]
+    fn default_matches_is_word_char() {
+        assert_eq!( words(WordCharSet::default()),
+                    &["it's", "well", "known", "state", "id", "3", "14"] );
+
+        let set = WordCharSet::default();
+        for c in "aZé9٣'’-_. \t".chars() {
+            assert_eq!( set.contains(c), is_word_char(c) );
+        }
+    }
+
+    #[test]
+    fn hyphens_and_underscores() {
+        assert_eq!( words(WordCharSet::default().hyphens(true).underscores(true)),
+                    &["it's", "well-known", "state_id", "3", "14"] );
+    }
+
+    #[test]
+    fn letters_or_digits_only() {
+        assert_eq!( words(WordCharSet::empty().letters(true)),
+                    &["it", "s", "well", "known", "state", "id"] );
+        assert_eq!( words(WordCharSet::empty().digits(true)), &["3", "14"] );
+        assert_eq!( words(WordCharSet::empty()), Vec::<String>::new() );
+    }
+
+    fn words(set: WordCharSet) -> Vec<String> {
+        Words::new(INPUT.as_bytes(), set.into_fn()).map(Result::unwrap).collect()
+    }
+}
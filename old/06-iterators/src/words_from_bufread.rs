@@ -1,21 +1,68 @@
 use super::{Iter8or, IntoIter8or, vec::VecIntoIter};
 
+use std::collections::HashMap;
 use std::io;
 
 pub struct Words<R, IsWordChar> {
-    lines: io::Lines<R>,
-    words: VecIntoIter<String>,
-    pred:  IsWordChar,
+    lines:     io::Lines<R>,
+    words:     VecIntoIter<String>,
+    pred:      IsWordChar,
+    lowercase: bool,
+    min_len:   usize,
+    trim:      bool,
+    at_start:  bool,
 }
 
 impl<R: io::BufRead, IsWordChar: Fn(char) -> bool> Words<R, IsWordChar> {
     pub fn new(input: R, pred: IsWordChar) -> Self {
         Words {
-            lines: input.lines(),
-            words: Vec::new().into_iter8or(),
-            pred
+            lines:     input.lines(),
+            words:     Vec::new().into_iter8or(),
+            pred,
+            lowercase: false,
+            min_len:   0,
+            trim:      false,
+            at_start:  true,
         }
     }
+
+    /// Folds each word to lowercase, using the full Unicode mapping of
+    /// `str::to_lowercase`, for counting words case-insensitively.
+    pub fn lowercase(self) -> Self {
+        Words { lowercase: true, ..self }
+    }
+
+    /// Skips words shorter than `min_len` characters (not bytes, so
+    /// accented letters count once).
+    pub fn min_len(self, min_len: usize) -> Self {
+        Words { min_len, ..self }
+    }
+
+    /// Strips apostrophes from the start and end of each word, keeping
+    /// those inside it. Thus `don't` stays whole, but `'quoted'` becomes
+    /// `quoted` and `dogs'` becomes `dogs`. A word made only of
+    /// apostrophes is skipped, and `min_len` counts what's left after
+    /// trimming.
+    pub fn trim_apostrophes(self) -> Self {
+        Words { trim: true, ..self }
+    }
+
+    fn split_line(&self, line: &str) -> VecIntoIter<String> {
+        line.split(|c| !(self.pred)(c))
+            .map(|s| if self.trim {
+                s.trim_matches(is_apostrophe)
+            } else {
+                s
+            })
+            .filter(|s| !s.is_empty() && s.chars().count() >= self.min_len)
+            .map(|s| if self.lowercase {
+                s.to_lowercase()
+            } else {
+                s.to_owned()
+            })
+            .collect::<Vec<_>>()
+            .into_iter8or()
+    }
 }
 
 impl<R, IsWordChar> Iter8or for Words<R, IsWordChar>
@@ -30,22 +77,270 @@ impl<R, IsWordChar> Iter8or for Words<R, IsWordChar>
                 return Some(Ok(word));
             } else {
                 match self.lines.next() {
-                    Some(Ok(line)) =>
-                        self.words = line.split(|c| !(self.pred)(c))
-                            .filter(|s| !s.is_empty())
-                            .map(ToOwned::to_owned)
-                            .collect::<Vec<_>>()
-                            .into_iter8or(),
+                    Some(Ok(line)) => {
+                        // Editors on Windows often start UTF-8 files with
+                        // a byte-order mark, which isn't part of the text.
+                        let text = if self.at_start {
+                            line.strip_prefix('\u{feff}').unwrap_or(&line)
+                        } else {
+                            &line
+                        };
+                        self.at_start = false;
+                        self.words = self.split_line(text);
+                    }
                     Some(Err(e)) => return Some(Err(e)),
                     None => return None,
                 }
             }
         }
     }
+
+    /// We can't know how many lines are left, but the words already
+    /// split from the current line are a lower bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.words.size_hint().0, None)
+    }
+}
+
+/// Counts how many times each word, as split by `is_word_char`, occurs
+/// in `input`.
+pub fn word_counts<R: io::Read>(input: R) -> io::Result<HashMap<String, usize>> {
+    tally(Words::new(io::BufReader::new(input), is_word_char))
+}
+
+/// Like `word_counts`, but case-insensitive: words are folded to
+/// lowercase before they're counted.
+pub fn word_counts_lowercase<R: io::Read>(input: R) -> io::Result<HashMap<String, usize>> {
+    tally(Words::new(io::BufReader::new(input), is_word_char).lowercase())
+}
+
+fn tally<I>(mut words: I) -> io::Result<HashMap<String, usize>>
+    where I: Iter8or<Item = io::Result<String>>
+{
+    let mut counts = HashMap::new();
+
+    while let Some(word) = words.next() {
+        *counts.entry(word?).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+/// Where a word starts in the input: `line` counts from 1, and `column`
+/// is the byte offset of the word within its line, counting from 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub line:   usize,
+    pub column: usize,
+}
+
+/// What comes right after a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WordTerminator {
+    /// Some other character on the same line, such as a space or
+    /// punctuation.
+    Space,
+    /// The end of the line.
+    Newline,
+    /// The end of the input, with no newline first.
+    Eof,
+}
+
+/// Like `Words`, but yields each word along with its `Position`. An
+/// error from the reader ends the iteration, since the positions after
+/// it couldn't be trusted.
+pub struct WordsWithPosition<R, IsWordChar> {
+    input:  R,
+    line:   usize,
+    offset: usize,
+    words:  VecIntoIter<Located>,
+    pred:   IsWordChar,
+    // After an error we can't tell how much of the input `read_line`
+    // consumed, so positions from then on would be wrong. Instead, the
+    // error ends the iteration.
+    failed: bool,
+}
+
+/// Everything we know about where a word came from. Each of the
+/// positional iterators exposes part of it.
+struct Located {
+    word:       String,
+    position:   Position,
+    offset:     usize,
+    terminator: WordTerminator,
+}
+
+impl<R: io::BufRead, IsWordChar: Fn(char) -> bool> WordsWithPosition<R, IsWordChar> {
+    pub fn new(input: R, pred: IsWordChar) -> Self {
+        WordsWithPosition {
+            input,
+            line:   0,
+            offset: 0,
+            words:  Vec::new().into_iter8or(),
+            pred,
+            failed: false,
+        }
+    }
+
+    /// Yields the next word with its position, its byte offset from the
+    /// start of the input, and its terminator.
+    fn next_located(&mut self) -> Option<io::Result<Located>> {
+        loop {
+            if let Some(word) = self.words.next() {
+                return Some(Ok(word));
+            }
+
+            if self.failed {
+                return None;
+            }
+
+            let mut line = String::new();
+            match self.input.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(len) => {
+                    self.line += 1;
+                    let line_number = self.line;
+                    let line_offset = self.offset;
+                    self.offset += len;
+                    let text = trim_newline(&line);
+                    let at_end = if text.len() < line.len() {
+                        WordTerminator::Newline
+                    } else {
+                        WordTerminator::Eof
+                    };
+                    self.words = split_words(text, &self.pred)
+                        .into_iter()
+                        .map(|(column, word)| {
+                            let terminator = if column + word.len() < text.len() {
+                                WordTerminator::Space
+                            } else {
+                                at_end
+                            };
+                            Located {
+                                word,
+                                position: Position { line: line_number, column },
+                                offset:   line_offset + column,
+                                terminator,
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .into_iter8or();
+                }
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<R, IsWordChar> Iter8or for WordsWithPosition<R, IsWordChar>
+    where R: io::BufRead,
+          IsWordChar: Fn(char) -> bool
+{
+    type Item = io::Result<(String, Position)>;
+
+    fn next(&mut self) -> Option<io::Result<(String, Position)>> {
+        self.next_located()
+            .map(|result| result.map(|located| (located.word, located.position)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.words.size_hint().0, None)
+    }
+}
+
+/// Like `Words`, but yields each word along with the byte offset where
+/// it starts, counting from the start of the whole input.
+pub struct WordsWithOffset<R, IsWordChar>(WordsWithPosition<R, IsWordChar>);
+
+impl<R: io::BufRead, IsWordChar: Fn(char) -> bool> WordsWithOffset<R, IsWordChar> {
+    pub fn new(input: R, pred: IsWordChar) -> Self {
+        WordsWithOffset(WordsWithPosition::new(input, pred))
+    }
+}
+
+impl<R, IsWordChar> Iter8or for WordsWithOffset<R, IsWordChar>
+    where R: io::BufRead,
+          IsWordChar: Fn(char) -> bool
+{
+    type Item = io::Result<(String, usize)>;
+
+    fn next(&mut self) -> Option<io::Result<(String, usize)>> {
+        self.0.next_located()
+            .map(|result| result.map(|located| (located.word, located.offset)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Like `Words`, but yields each word along with what came after it,
+/// for reconstructing the layout of the text.
+pub struct WordsWithTerminator<R, IsWordChar>(WordsWithPosition<R, IsWordChar>);
+
+impl<R: io::BufRead, IsWordChar: Fn(char) -> bool> WordsWithTerminator<R, IsWordChar> {
+    pub fn new(input: R, pred: IsWordChar) -> Self {
+        WordsWithTerminator(WordsWithPosition::new(input, pred))
+    }
+}
+
+impl<R, IsWordChar> Iter8or for WordsWithTerminator<R, IsWordChar>
+    where R: io::BufRead,
+          IsWordChar: Fn(char) -> bool
+{
+    type Item = io::Result<(String, WordTerminator)>;
+
+    fn next(&mut self) -> Option<io::Result<(String, WordTerminator)>> {
+        self.0.next_located()
+            .map(|result| result.map(|located| (located.word, located.terminator)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Removes the line terminator, `"\n"` or `"\r\n"`, that `read_line`
+/// leaves on, as `lines` does.
+fn trim_newline(line: &str) -> &str {
+    line.strip_suffix("\r\n")
+        .or_else(|| line.strip_suffix('\n'))
+        .unwrap_or(line)
+}
+
+/// Splits `line` into words, pairing each with the byte offset where
+/// it starts.
+fn split_words<P: Fn(char) -> bool>(line: &str, pred: P) -> Vec<(usize, String)> {
+    let mut result = Vec::new();
+    let mut start  = None;
+
+    for (i, c) in line.char_indices() {
+        match (pred(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(j)) => {
+                result.push((j, line[j .. i].to_owned()));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    if let Some(j) = start {
+        result.push((j, line[j ..].to_owned()));
+    }
+
+    result
 }
 
 pub fn is_word_char(c: char) -> bool {
-    c.is_alphanumeric() || c == '\'' || c == '’'
+    c.is_alphanumeric() || is_apostrophe(c)
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
 }
 
 #[cfg(test)]
@@ -66,6 +361,223 @@ mod tests {
         assert_words(" - ", &[]);
     }
 
+    #[test]
+    fn trimmed_apostrophes() {
+        use super::{Words, is_word_char};
+
+        let input = "don't 'quoted' dogs' ’tis ' ''";
+        let actual: Vec<String> =
+            Words::new(input.as_bytes(), is_word_char)
+                .trim_apostrophes()
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!( actual, &["don't", "quoted", "dogs", "tis"] );
+
+        assert_words(input, &["don't", "'quoted'", "dogs'", "’tis", "'", "''"]);
+    }
+
+    #[test]
+    fn combined_options() {
+        use super::{Words, is_word_char};
+
+        let actual: Vec<String> =
+            Words::new("'Don't' I 'AM' dogs' Cat".as_bytes(), is_word_char)
+                .lowercase()
+                .min_len(3)
+                .trim_apostrophes()
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!( actual, &["don't", "dogs", "cat"] );
+    }
+
+    #[test]
+    fn min_length() {
+        use super::{Words, is_word_char};
+
+        let words = |input: &str, min_len| -> Vec<String> {
+            Words::new(input.as_bytes(), is_word_char)
+                .min_len(min_len)
+                .map(Result::unwrap)
+                .collect()
+        };
+
+        assert_eq!( words("a an the", 2), &["an", "the"] );
+        assert_eq!( words("a an the", 0), &["a", "an", "the"] );
+        assert_eq!( words("a an the", 4), Vec::<String>::new() );
+        // "é" and "né" are 2 and 3 bytes, but 1 and 2 characters.
+        assert_eq!( words("é né x", 2), &["né"] );
+    }
+
+    #[test]
+    fn strips_leading_bom() {
+        use super::Words;
+
+        let not_space = |c: char| !c.is_whitespace();
+        let words = |input: &str| -> Vec<String> {
+            Words::new(input.as_bytes(), not_space).map(Result::unwrap).collect()
+        };
+
+        assert_eq!( words("\u{feff}hello world"), &["hello", "world"] );
+        assert_eq!( words("\u{feff}"), Vec::<String>::new() );
+        assert_eq!( words("\u{feff}\n"), Vec::<String>::new() );
+        // Only a mark at the very start is stripped.
+        assert_eq!( words("a\n\u{feff}b"), &["a", "\u{feff}b"] );
+
+        assert_words("\u{feff}hello world", &["hello", "world"]);
+    }
+
+    #[test]
+    fn size_hint_counts_buffered_words() {
+        use super::{Words, is_word_char};
+
+        let mut words = Words::new("one two three\nfour".as_bytes(), is_word_char);
+        assert_eq!( words.size_hint(), (0, None) );
+
+        words.next();
+        assert_eq!( words.size_hint(), (2, None) );
+        words.next();
+        words.next();
+        assert_eq!( words.size_hint(), (0, None) );
+
+        words.next();
+        assert_eq!( words.size_hint(), (0, None) );
+        assert!( words.next().is_none() );
+    }
+
+    #[test]
+    fn lowercase() {
+        use super::{Words, is_word_char};
+
+        let actual: Vec<String> =
+            Words::new("Hello HELLO héllo HÉLLO".as_bytes(), is_word_char)
+                .lowercase()
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!( actual, &["hello", "hello", "héllo", "héllo"] );
+    }
+
+    #[test]
+    fn counts() {
+        use super::{word_counts, word_counts_lowercase};
+
+        let counts = word_counts("the cat The dog the".as_bytes()).unwrap();
+        assert_eq!( counts.len(), 4 );
+        assert_eq!( counts["the"], 2 );
+        assert_eq!( counts["The"], 1 );
+        assert_eq!( counts["cat"], 1 );
+        assert_eq!( counts["dog"], 1 );
+
+        let counts = word_counts_lowercase("the cat The dog the".as_bytes()).unwrap();
+        assert_eq!( counts.len(), 3 );
+        assert_eq!( counts["the"], 3 );
+    }
+
+    #[test]
+    fn counts_propagate_errors() {
+        use super::word_counts;
+        use std::io;
+
+        let err = word_counts(&b"fine\nnot \xff utf-8"[..]).unwrap_err();
+        assert_eq!( err.kind(), io::ErrorKind::InvalidData );
+    }
+
+    #[test]
+    fn terminators() {
+        use super::{WordsWithTerminator, WordTerminator, is_word_char};
+        use super::WordTerminator::*;
+
+        let terminators = |input: &str| -> Vec<(String, WordTerminator)> {
+            WordsWithTerminator::new(input.as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .collect()
+        };
+        let expected = |pairs: &[(&str, WordTerminator)]| -> Vec<(String, WordTerminator)> {
+            pairs.iter().map(|&(word, t)| (word.to_owned(), t)).collect()
+        };
+
+        assert_eq!( terminators("hello world\r\nbye, now"),
+                    expected(&[("hello", Space), ("world", Newline),
+                               ("bye", Space), ("now", Eof)]) );
+        assert_eq!( terminators("done.\nend\n"),
+                    expected(&[("done", Space), ("end", Newline)]) );
+    }
+
+    #[test]
+    fn positions() {
+        use super::{WordsWithPosition, Position, is_word_char};
+
+        let input = "hello, world\r\n  two  lines\nthree";
+        let actual: Vec<(String, Position)> =
+            WordsWithPosition::new(input.as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .collect();
+        let expected: Vec<(String, Position)> = vec![
+            ("hello".to_owned(), Position { line: 1, column: 0 }),
+            ("world".to_owned(), Position { line: 1, column: 7 }),
+            ("two".to_owned(),   Position { line: 2, column: 2 }),
+            ("lines".to_owned(), Position { line: 2, column: 7 }),
+            ("three".to_owned(), Position { line: 3, column: 0 }),
+        ];
+        assert_eq!( actual, expected );
+    }
+
+    #[test]
+    fn offsets() {
+        use super::{WordsWithOffset, is_word_char};
+
+        // `—` and `«` are multi-byte separators.
+        let input = "one—two\r\n«three» four\nfive";
+        let actual: Vec<(String, usize)> =
+            WordsWithOffset::new(input.as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .collect();
+        let expected: Vec<(String, usize)> = vec![
+            ("one".to_owned(),   0),
+            ("two".to_owned(),   6),
+            ("three".to_owned(), 13),
+            ("four".to_owned(),  21),
+            ("five".to_owned(),  26),
+        ];
+        assert_eq!( actual, expected );
+
+        for (word, offset) in actual {
+            assert_eq!( &input[offset .. offset + word.len()], word );
+        }
+    }
+
+    #[test]
+    fn offsets_stop_after_error() {
+        use super::{WordsWithOffset, is_word_char};
+        use std::io::{self, Read};
+
+        // Fails once in the middle of the second line, then would go on.
+        struct Flaky(Vec<io::Result<&'static [u8]>>);
+
+        impl Read for Flaky {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.0.remove(0)?;
+                buf[.. chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let input = Flaky(vec![
+            Ok(b"one two\nthr"),
+            Err(io::Error::other("flaky")),
+            Ok(b"ee four\n"),
+        ]);
+        let mut words = WordsWithOffset::new(io::BufReader::new(input), is_word_char);
+
+        assert_eq!( words.next().unwrap().unwrap(), ("one".to_owned(), 0) );
+        assert_eq!( words.next().unwrap().unwrap(), ("two".to_owned(), 4) );
+        assert_eq!( words.next().unwrap().unwrap_err().kind(), io::ErrorKind::Other );
+        assert!( words.next().is_none() );
+        assert!( words.next().is_none() );
+    }
+
     fn assert_words(input: &str, expected_words: &[&str]) {
         use super::{Words, is_word_char};
         let actual_words: Vec<String> =
@@ -0,0 +1,209 @@
+use super::{Iter8or, IntoIter8or, vec::VecIntoIter};
+
+use std::{io, mem, str};
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Like `Words`, but reads the input in fixed-size chunks rather than
+/// whole lines, so memory use stays bounded even if the input has no
+/// newlines at all. Besides the chunk buffer, it holds only the words
+/// split from the current chunk and the start of a word that continues
+/// into the next chunk. A word or a UTF-8 character that straddles two
+/// chunks is reassembled.
+pub struct StreamWords<R, IsWordChar> {
+    input:   R,
+    chunk:   Box<[u8]>,
+    // The bytes of a character cut off at the end of the last chunk.
+    partial: Vec<u8>,
+    word:    String,
+    words:   VecIntoIter<String>,
+    pred:    IsWordChar,
+    eof:     bool,
+    // Invalid UTF-8 ends the input, but we report it only after the
+    // words that came before it.
+    error:   Option<io::Error>,
+}
+
+impl<R: io::Read, IsWordChar: Fn(char) -> bool> StreamWords<R, IsWordChar> {
+    pub fn new(input: R, pred: IsWordChar) -> Self {
+        StreamWords::with_chunk_size(input, pred, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn with_chunk_size(input: R, pred: IsWordChar, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "StreamWords::with_chunk_size: chunk_size must be positive");
+
+        StreamWords {
+            input,
+            chunk:   vec![0; chunk_size].into_boxed_slice(),
+            partial: Vec::new(),
+            word:    String::new(),
+            words:   Vec::new().into_iter8or(),
+            pred,
+            eof:     false,
+            error:   None,
+        }
+    }
+
+    /// Reads the next chunk, splitting the words it finishes into
+    /// `self.words`. Only errors from the reader itself are returned.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let len = loop {
+            match self.input.read(&mut self.chunk) {
+                Ok(len) => break len,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        };
+
+        let mut finished = Vec::new();
+
+        if len == 0 {
+            self.eof = true;
+
+            if !self.partial.is_empty() {
+                self.error = Some(invalid_utf8());
+            }
+        } else {
+            self.partial.extend_from_slice(&self.chunk[.. len]);
+
+            let valid_len = match str::from_utf8(&self.partial) {
+                Ok(text) => text.len(),
+                Err(e) => match e.error_len() {
+                    // Only the end is incomplete; the rest may come
+                    // with the next chunk.
+                    None => e.valid_up_to(),
+                    Some(_) => {
+                        self.eof = true;
+                        self.error = Some(invalid_utf8());
+                        e.valid_up_to()
+                    }
+                },
+            };
+
+            // We've just checked that this much is valid.
+            let text = str::from_utf8(&self.partial[.. valid_len]).unwrap();
+            for c in text.chars() {
+                if (self.pred)(c) {
+                    self.word.push(c);
+                } else if !self.word.is_empty() {
+                    finished.push(mem::take(&mut self.word));
+                }
+            }
+
+            self.partial.drain(.. valid_len);
+        }
+
+        if self.eof && !self.word.is_empty() {
+            finished.push(mem::take(&mut self.word));
+        }
+
+        self.words = finished.into_iter8or();
+        Ok(())
+    }
+}
+
+impl<R, IsWordChar> Iter8or for StreamWords<R, IsWordChar>
+    where R: io::Read,
+          IsWordChar: Fn(char) -> bool
+{
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        loop {
+            if let Some(word) = self.words.next() {
+                return Some(Ok(word));
+            }
+
+            if let Some(e) = self.error.take() {
+                return Some(Err(e));
+            }
+
+            if self.eof {
+                return None;
+            }
+
+            if let Err(e) = self.read_chunk() {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.words.size_hint().0, None)
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamWords;
+    use super::super::Iter8or;
+    use super::super::words_from_bufread::{Words, is_word_char};
+
+    use std::io;
+
+    /// A reader that returns at most `max` bytes per read, to exercise
+    /// chunk boundaries regardless of the chunk size.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        max:   usize,
+    }
+
+    impl<'a> io::Read for Trickle<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.max.min(buf.len()).min(self.bytes.len());
+            buf[.. len].copy_from_slice(&self.bytes[.. len]);
+            self.bytes = &self.bytes[len ..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn words_straddling_chunks_are_reassembled() {
+        let input = "hello, wörld — naïve\ncafé crème 42";
+        let expected: Vec<String> = Words::new(input.as_bytes(), is_word_char)
+            .map(Result::unwrap)
+            .collect();
+
+        for max in 1 .. 8 {
+            let reader = Trickle { bytes: input.as_bytes(), max };
+            let actual: Vec<String> = StreamWords::new(reader, is_word_char)
+                .map(Result::unwrap)
+                .collect();
+            assert_eq!( actual, expected, "with reads of up to {} bytes", max );
+        }
+
+        for chunk_size in 1 .. 8 {
+            let actual: Vec<String> =
+                StreamWords::with_chunk_size(input.as_bytes(), is_word_char, chunk_size)
+                    .map(Result::unwrap)
+                    .collect();
+            assert_eq!( actual, expected, "with chunks of {} bytes", chunk_size );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!( StreamWords::new(&b""[..], is_word_char).next().is_none() );
+        assert!( StreamWords::new(&b" - "[..], is_word_char).next().is_none() );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut truncated = StreamWords::new(&b"ok\xc3"[..], is_word_char);
+        assert_eq!( truncated.next().unwrap().unwrap(), "ok" );
+        assert_eq!( truncated.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData );
+        assert!( truncated.next().is_none() );
+
+        let mut invalid = StreamWords::new(&b"ok \xff ok"[..], is_word_char);
+        assert_eq!( invalid.next().unwrap().unwrap(), "ok" );
+        assert_eq!( invalid.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData );
+        assert!( invalid.next().is_none() );
+    }
+}
@@ -0,0 +1,83 @@
+use super::Iter8or;
+
+/// Words from a string we already hold in memory. Unlike the other word
+/// iterators, this doesn't allocate: each word is a slice of the input.
+pub struct StrWords<'a, IsWordChar> {
+    rest: &'a str,
+    pred: IsWordChar,
+}
+
+impl<'a, IsWordChar: Fn(char) -> bool> StrWords<'a, IsWordChar> {
+    pub fn new(input: &'a str, pred: IsWordChar) -> Self {
+        StrWords {
+            rest: input,
+            pred
+        }
+    }
+}
+
+impl<'a, IsWordChar> Iter8or for StrWords<'a, IsWordChar>
+    where IsWordChar: Fn(char) -> bool
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let start = self.rest.find(|c| (self.pred)(c))?;
+        let rest  = &self.rest[start ..];
+        let end   = rest.find(|c| !(self.pred)(c)).unwrap_or(rest.len());
+
+        self.rest = &rest[end ..];
+        Some(&rest[.. end])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Every word but the last needs a separator after it.
+        let len = self.rest.len();
+        (0, Some(len.div_ceil(2)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StrWords;
+    use super::super::Iter8or;
+    use super::super::words_from_bufread::{self, is_word_char};
+
+    #[test]
+    fn hello_world() {
+        assert_words("hello world", &["hello", "world"]);
+        assert_words("hello, world", &["hello", "world"]);
+        assert_words("   hello, world!   ", &["hello", "world"]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_words("", &[]);
+        assert_words("  ", &[]);
+        assert_words(" - ", &[]);
+    }
+
+    #[test]
+    fn slices_point_into_input() {
+        let input = "Ça va?\n— très bien, merci";
+        let base  = input.as_ptr() as usize;
+
+        let mut words = StrWords::new(input, is_word_char);
+        while let Some(word) = words.next() {
+            let offset = word.as_ptr() as usize - base;
+            assert_eq!( &input[offset .. offset + word.len()], word );
+        }
+
+        let borrowed: Vec<&str> = StrWords::new(input, is_word_char).collect();
+        let owned: Vec<String> =
+            words_from_bufread::Words::new(input.as_bytes(), is_word_char)
+                .map(Result::unwrap)
+                .collect();
+        assert_eq!( borrowed, owned );
+    }
+
+    fn assert_words(input: &str, expected_words: &[&str]) {
+        let actual_words: Vec<&str> = StrWords::new(input, is_word_char).collect();
+        assert_eq!( actual_words, expected_words );
+    }
+}
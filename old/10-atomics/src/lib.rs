@@ -1,4 +1,6 @@
 extern crate crossbeam_epoch as epoch;
+#[cfg(loom)]
+extern crate loom;
 
 pub mod shared_vars;
 pub mod stacks;
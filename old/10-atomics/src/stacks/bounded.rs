@@ -0,0 +1,135 @@
+//! Lock-free stacks with a maximum capacity.
+
+use super::treiber::TreiberStack;
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{AcqRel, Acquire};
+
+/// A lock-free stack that refuses pushes beyond a fixed capacity.
+///
+/// This can be shared between threads by wrapping it in an `Arc`.
+#[derive(Debug)]
+pub struct BoundedStack<T> {
+    stack:    TreiberStack<T>,
+    reserved: AtomicUsize,
+    capacity: usize,
+}
+// Invariants:
+//  - self.stack.len() <= self.reserved <= self.capacity
+//
+// A push reserves a slot before touching the stack, and a pop releases
+// its slot only after removing an element, so the number of elements
+// can never exceed the capacity, even with pushes racing.
+
+impl<T> BoundedStack<T> {
+    /// Returns a new, empty stack that holds at most `capacity`
+    /// elements.
+    pub fn new(capacity: usize) -> Self {
+        BoundedStack {
+            stack:    TreiberStack::new(),
+            reserved: AtomicUsize::new(0),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of elements the stack can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Checks whether the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Returns a snapshot of the number of elements in the stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Pushes an element on top of the stack if there's room, or hands
+    /// it back as `Err` if the stack is full.
+    pub fn try_push(&self, data: T) -> Result<(), T> {
+        let mut reserved = self.reserved.load(Acquire);
+
+        loop {
+            if reserved >= self.capacity {
+                return Err(data);
+            }
+
+            match self.reserved.compare_exchange_weak(reserved, reserved + 1,
+                                                       AcqRel, Acquire) {
+                Ok(_) => break,
+                Err(actual) => reserved = actual,
+            }
+        }
+
+        self.stack.push(data);
+        Ok(())
+    }
+
+    /// Removes and returns the top element of the stack, or `None` if
+    /// empty.
+    pub fn pop(&self) -> Option<T> {
+        let result = self.stack.pop();
+
+        if result.is_some() {
+            self.reserved.fetch_sub(1, AcqRel);
+        }
+
+        result
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn refuses_pushes_when_full() {
+    let stack = BoundedStack::new(2);
+
+    assert_eq!(Ok(()), stack.try_push(1));
+    assert_eq!(Ok(()), stack.try_push(2));
+    assert_eq!(Err(3), stack.try_push(3));
+    assert_eq!(2, stack.len());
+
+    assert_eq!(Some(2), stack.pop());
+    assert_eq!(Ok(()), stack.try_push(4));
+    assert_eq!(Err(5), stack.try_push(5));
+    assert_eq!(Some(4), stack.pop());
+    assert_eq!(Some(1), stack.pop());
+    assert_eq!(None, stack.pop());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn never_exceeds_capacity() {
+    use std::{sync, thread};
+
+    const CAPACITY: usize = 16;
+
+    let stack = sync::Arc::new(BoundedStack::new(CAPACITY));
+
+    let handles: Vec<_> = (0 .. 8).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            for i in 0 .. 2000 {
+                let _ = stack.try_push(i);
+                assert!(stack.len() <= CAPACITY);
+                if (i + t) % 3 == 0 {
+                    stack.pop();
+                }
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(stack.len() <= CAPACITY);
+
+    let mut remaining = 0;
+    while stack.pop().is_some() {
+        remaining += 1;
+    }
+    assert!(remaining <= CAPACITY);
+}
@@ -0,0 +1,342 @@
+//! Lock-free stacks with an elimination layer for high contention.
+//!
+//! This follows Hendler, Shavit, and Yerushalmi, “A Scalable Lock-free
+//! Stack Algorithm” (SPAA 2004).
+
+use super::treiber::TreiberStack;
+
+use std::cell::Cell;
+use std::hint::spin_loop;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::Ordering::{Acquire, Release, Relaxed};
+
+use epoch::{self, Atomic, Guard, Owned, Shared};
+
+/// How many times a push waits for a taker after offering its element.
+const OFFER_SPINS: usize = 64;
+
+/// The number of elimination slots that `EliminationStack::new` uses.
+const DEFAULT_SLOTS: usize = 8;
+
+/// A lock-free stack that lets colliding pushes and pops trade elements
+/// directly instead of retrying on the head.
+///
+/// When a push loses the race to update the head, it offers its element
+/// in a randomly chosen slot and waits briefly. When a pop loses the
+/// race, it checks a random slot and takes any element on offer. A push
+/// and a pop that meet this way cancel out without touching the head,
+/// which relieves contention when pushes and pops are balanced. An
+/// operation that finds no partner goes back to the head and tries
+/// again.
+///
+/// This can be shared between threads by wrapping it in an `Arc`.
+#[derive(Debug)]
+pub struct EliminationStack<T> {
+    stack: TreiberStack<T>,
+    slots: Box<[Atomic<ManuallyDrop<T>>]>,
+}
+// Invariants:
+//  - A non-null slot points to an element that some push is waiting to
+//    hand off. Whoever swaps the slot back to null owns the element:
+//    a pop that does so returns it, and a push that does so withdraws
+//    it and retries on the head.
+//
+// The winner moves the element out and leaves the box to the epoch
+// collector. The waiting push stays pinned from its offer until it
+// returns, so its box can't be freed, and its address reused for
+// another offer, while it's still comparing against it.
+
+impl<T> EliminationStack<T> {
+    /// Returns a new, empty stack with a default number of elimination
+    /// slots.
+    pub fn new() -> Self {
+        EliminationStack::with_slots(DEFAULT_SLOTS)
+    }
+
+    /// Returns a new, empty stack with `slots` elimination slots. More
+    /// slots suit more threads, but make it less likely that a push and
+    /// a pop find each other.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slots` is 0.
+    pub fn with_slots(slots: usize) -> Self {
+        assert!(slots > 0, "EliminationStack::with_slots: need at least one slot");
+
+        EliminationStack {
+            stack: TreiberStack::new(),
+            slots: (0 .. slots).map(|_| Atomic::null()).collect(),
+        }
+    }
+
+    /// Checks whether the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Returns a snapshot of the number of elements in the stack.
+    ///
+    /// Elements on offer in the elimination slots aren't counted, since
+    /// their pushes haven't finished.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Pushes an element on top of the stack.
+    pub fn push(&self, mut data: T) {
+        loop {
+            data = match self.stack.try_push(data) {
+                Ok(()) => return,
+                Err(data) => data,
+            };
+
+            data = match self.offer(data) {
+                Ok(()) => return,
+                Err(data) => data,
+            };
+        }
+    }
+
+    /// Removes and returns the top element of the stack, or `None` if
+    /// empty.
+    pub fn pop(&self) -> Option<T> {
+        loop {
+            if let Ok(result) = self.stack.try_pop() {
+                return result;
+            }
+
+            if let Some(data) = self.take() {
+                return Some(data);
+            }
+        }
+    }
+
+    /// Offers `data` to a concurrent pop, handing it back as `Err` if no
+    /// pop takes it in time.
+    fn offer(&self, data: T) -> Result<(), T> {
+        let guard = epoch::pin();
+        let slot = self.random_slot();
+
+        // `Release` publishes the element to the pop that takes it.
+        let offer = match slot.compare_exchange(Shared::null(),
+                                                Owned::new(ManuallyDrop::new(data)),
+                                                Release, Relaxed, &guard) {
+            Ok(offer) => offer,
+            Err(err) => return Err(ManuallyDrop::into_inner(*err.new.into_box())),
+        };
+
+        for _ in 0 .. OFFER_SPINS {
+            // We only compare the pointer, so `Relaxed` is enough.
+            if slot.load(Relaxed, &guard) != offer {
+                return Ok(());
+            }
+
+            spin_loop();
+        }
+
+        // If this succeeds, the element is the one we wrote, so there's
+        // nothing to acquire.
+        match slot.compare_exchange(offer, Shared::null(), Relaxed, Relaxed, &guard) {
+            Ok(_) => Err(unsafe { claim(offer, &guard) }),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Takes an element on offer from a concurrent push, if one is
+    /// waiting in the slot we look at.
+    fn take(&self) -> Option<T> {
+        let guard = epoch::pin();
+        let slot = self.random_slot();
+
+        let offer = slot.load(Relaxed, &guard);
+        if offer.is_null() {
+            spin_loop();
+            return None;
+        }
+
+        // `Acquire` synchronizes with the `Release` that offered it.
+        match slot.compare_exchange(offer, Shared::null(), Acquire, Relaxed, &guard) {
+            Ok(_) => Some(unsafe { claim(offer, &guard) }),
+            Err(_) => None,
+        }
+    }
+
+    fn random_slot(&self) -> &Atomic<ManuallyDrop<T>> {
+        &self.slots[random_index(self.slots.len())]
+    }
+}
+
+/// Moves the element out of an offer whose slot we just swapped to null,
+/// deferring the box's reclamation until no other thread can be looking
+/// at it.
+unsafe fn claim<T>(offer: Shared<ManuallyDrop<T>>, guard: &Guard) -> T {
+    let data = ManuallyDrop::into_inner(ptr::read(offer.deref()));
+    guard.defer_destroy(offer);
+    data
+}
+
+impl<T> Default for EliminationStack<T> {
+    fn default() -> Self {
+        EliminationStack::new()
+    }
+}
+
+impl<T> Drop for EliminationStack<T> {
+    fn drop(&mut self) {
+        // Every push withdraws its offer or sees it taken before
+        // returning, so the slots should be empty by now, but free
+        // anything left just in case.
+        unsafe {
+            let guard = epoch::unprotected();
+
+            for slot in self.slots.iter() {
+                let offer = slot.load(Relaxed, guard);
+                if !offer.is_null() {
+                    ManuallyDrop::drop(&mut *offer.into_owned());
+                }
+            }
+        }
+    }
+}
+
+/// Returns a pseudorandom index in `0 .. n`, using a per-thread xorshift
+/// generator. Different threads need only pick different slots often,
+/// not unpredictably.
+fn random_index(n: usize) -> usize {
+    thread_local! {
+        static STATE: Cell<u32> = const { Cell::new(0) };
+    }
+
+    STATE.with(|state| {
+        let mut x = state.get();
+        if x == 0 {
+            // Seed from the address of this thread's state, which
+            // differs between threads.
+            x = state as *const Cell<u32> as usize as u32 | 1;
+        }
+
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        state.set(x);
+
+        x as usize % n
+    })
+}
+
+#[cfg(not(loom))]
+#[test]
+fn push_and_pop_in_lifo_order() {
+    let stack = EliminationStack::new();
+
+    assert!(stack.is_empty());
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+    assert_eq!(3, stack.len());
+
+    assert_eq!(Some(3), stack.pop());
+    assert_eq!(Some(2), stack.pop());
+    assert_eq!(Some(1), stack.pop());
+    assert_eq!(None, stack.pop());
+}
+
+/// Runs symmetric push/pop load against a stack from several threads,
+/// then drains it, returning every element popped, sorted. If the stack
+/// is correct that's every element pushed.
+#[cfg(all(test, not(loom)))]
+fn symmetric_load<S>(stack: S, push: fn(&S, usize), pop: fn(&S) -> Option<usize>)
+                     -> Vec<usize>
+    where S: Send + Sync + 'static
+{
+    use std::{sync, thread};
+
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 10_000;
+
+    let stack = sync::Arc::new(stack);
+
+    let handles: Vec<_> = (0 .. THREADS).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            let mut popped = Vec::new();
+            for i in 0 .. PER_THREAD {
+                push(&stack, t * PER_THREAD + i);
+                popped.extend(pop(&stack));
+            }
+            popped
+        })
+    }).collect();
+
+    let mut actual: Vec<usize> = handles.into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    while let Some(element) = pop(&stack) {
+        actual.push(element);
+    }
+    actual.sort();
+
+    assert_eq!((0 .. THREADS * PER_THREAD).collect::<Vec<_>>(), actual);
+    actual
+}
+
+#[cfg(not(loom))]
+#[test]
+fn elimination_agrees_with_plain_treiber() {
+    let plain = symmetric_load(TreiberStack::new(),
+                               |stack, n| stack.push(n),
+                               |stack| stack.pop());
+    let eliminating = symmetric_load(EliminationStack::with_slots(2),
+                                     |stack, n| stack.push(n),
+                                     |stack| stack.pop());
+
+    assert_eq!(plain, eliminating);
+}
+
+#[cfg(not(loom))]
+#[test]
+fn pop_after_push_never_sees_empty() {
+    use std::{sync, thread};
+
+    // Every thread pops only right after pushing, so each pop runs while
+    // the stack holds at least the element just pushed. If an element
+    // could go missing between a push returning and a pop taking it,
+    // some pop here would return `None`.
+    let stack = sync::Arc::new(EliminationStack::with_slots(1));
+
+    let handles: Vec<_> = (0 .. 8).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            for i in 0 .. 10_000 {
+                stack.push(Box::new(t * 10_000 + i));
+                assert!(stack.pop().is_some());
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(stack.is_empty());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn drop_frees_elements() {
+    use std::sync::Arc;
+
+    let counter = Arc::new(());
+
+    {
+        let stack = EliminationStack::new();
+        for _ in 0 .. 10 {
+            stack.push(counter.clone());
+        }
+        stack.pop();
+        assert_eq!(10, Arc::strong_count(&counter));
+    }
+
+    assert_eq!(1, Arc::strong_count(&counter));
+}
@@ -0,0 +1,207 @@
+//! Lock-free stacks that know their minimum element.
+
+use super::treiber::TreiberStack;
+
+/// A lock-free stack that can report its minimum element in O(1).
+///
+/// Each node records the minimum of itself and everything below it, so
+/// popping an element restores the previous minimum for free.
+///
+/// This can be shared between threads by wrapping it in an `Arc`.
+///
+/// Pushes and `min` read the entries of other threads in place, so a pop
+/// can't just move an element out while someone might be looking at it.
+/// Instead it returns a clone, and the original is dropped once no
+/// thread can still see it. That deferred drop may happen on another
+/// thread, after the stack is gone, which is why the elements must be
+/// `Send + 'static`.
+#[derive(Debug, Default)]
+pub struct MinTrackingStack<T> {
+    stack: TreiberStack<Entry<T>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    value: T,
+    // The least `value` from this entry down.
+    min:   T,
+}
+
+impl<T> MinTrackingStack<T> {
+    /// Returns a new, empty stack.
+    pub fn new() -> Self {
+        MinTrackingStack {
+            stack: TreiberStack::new(),
+        }
+    }
+
+    /// Checks whether the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Returns a snapshot of the number of elements in the stack.
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+}
+
+impl<T: Clone + Ord + Send + 'static> MinTrackingStack<T> {
+    /// Pushes an element on top of the stack.
+    pub fn push(&self, value: T) {
+        let entry = Entry {
+            min:   value.clone(),
+            value,
+        };
+
+        // The minimum is computed against whichever entry the push
+        // actually lands on, even if that changes under contention.
+        //
+        // Safe because we only ever pop with `pop_cloned`, which leaves
+        // each entry in place for as long as another thread might read
+        // it.
+        unsafe {
+            self.stack.push_with(entry, |entry, below| {
+                entry.min = match below {
+                    Some(below) if below.min < entry.value => below.min.clone(),
+                    _ => entry.value.clone(),
+                };
+            });
+        }
+    }
+
+    /// Removes and returns the top element of the stack, or `None` if
+    /// empty.
+    pub fn pop(&self) -> Option<T> {
+        self.stack.pop_cloned().map(|entry| entry.value)
+    }
+
+    /// Gets a clone of the least element in the stack, if there is one.
+    pub fn min(&self) -> Option<T> {
+        // Safe for the same reason as in `push`.
+        unsafe { self.stack.peek_with(|entry| entry.min.clone()) }
+    }
+
+    /// Gets a clone of the top element of the stack, if there is one.
+    pub fn peek(&self) -> Option<T> {
+        // Safe for the same reason as in `push`.
+        unsafe { self.stack.peek_with(|entry| entry.value.clone()) }
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn min_follows_pushes_and_pops() {
+    let stack = MinTrackingStack::new();
+    assert_eq!(None, stack.min());
+
+    stack.push(5);
+    assert_eq!(Some(5), stack.min());
+    stack.push(7);
+    assert_eq!(Some(5), stack.min());
+    stack.push(3);
+    assert_eq!(Some(3), stack.min());
+    stack.push(3);
+    assert_eq!(Some(3), stack.min());
+    stack.push(8);
+    assert_eq!(Some(3), stack.min());
+    assert_eq!(Some(8), stack.peek());
+
+    assert_eq!(Some(8), stack.pop());
+    assert_eq!(Some(3), stack.min());
+    assert_eq!(Some(3), stack.pop());
+    assert_eq!(Some(3), stack.min());
+    assert_eq!(Some(3), stack.pop());
+    assert_eq!(Some(5), stack.min());
+    assert_eq!(Some(7), stack.pop());
+    assert_eq!(Some(5), stack.min());
+    assert_eq!(Some(5), stack.pop());
+    assert_eq!(None, stack.min());
+    assert_eq!(None, stack.pop());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn min_is_consistent_under_contention() {
+    use std::{sync, thread};
+
+    let stack = sync::Arc::new(MinTrackingStack::new());
+
+    let handles: Vec<_> = (0 .. 4).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            for i in 0 .. 1000 {
+                stack.push((i * 7 + t) % 101);
+                if i % 3 == 0 {
+                    stack.pop();
+                }
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // Single-threaded now: the minimum reported before each pop must be
+    // the least of the elements remaining at that point.
+    let mut mins = Vec::new();
+    let mut values = Vec::new();
+    while let Some(min) = stack.min() {
+        mins.push(min);
+        values.push(stack.pop().unwrap());
+    }
+
+    for (i, &min) in mins.iter().enumerate() {
+        assert_eq!(values[i ..].iter().cloned().min(), Some(min));
+    }
+}
+
+// Pushes and `min` read entries that other threads are popping, so with a
+// heap-owning element type this is a use-after-free if the pops don't
+// leave those entries alone. Run it under Miri or AddressSanitizer to
+// check, e.g.:
+//
+//     RUSTFLAGS=-Zsanitizer=address cargo +nightly test \
+//         --target x86_64-unknown-linux-gnu --lib min_tracking
+#[cfg(not(loom))]
+#[test]
+fn heap_elements_survive_concurrent_pops() {
+    use std::{sync, thread};
+
+    const ROUNDS: usize = if cfg!(miri) { 50 } else { 2000 };
+
+    let stack = sync::Arc::new(MinTrackingStack::new());
+
+    let handles: Vec<_> = (0 .. 4).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            for i in 0 .. ROUNDS {
+                stack.push(format!("{:04}-{}", (i * 7 + t) % 101, t));
+                if let Some(min) = stack.min() {
+                    assert_eq!(6, min.len());
+                }
+                if i % 2 == 0 {
+                    assert!(stack.pop().is_some());
+                }
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(2 * ROUNDS, stack.len());
+
+    let mut mins = Vec::new();
+    let mut values = Vec::new();
+    while let Some(min) = stack.min() {
+        mins.push(min);
+        values.push(stack.pop().unwrap());
+    }
+
+    for (i, min) in mins.iter().enumerate() {
+        assert_eq!(values[i ..].iter().min(), Some(min));
+    }
+}
@@ -2,3 +2,6 @@ pub mod sequential;
 pub mod coarse;
 pub mod read_write;
 pub mod treiber;
+pub mod bounded;
+pub mod elimination;
+pub mod min_tracking;
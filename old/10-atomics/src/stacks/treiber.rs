@@ -3,11 +3,44 @@
 //! This code is based on [an article by Aaron
 //! Turon](https://aturon.github.io/blog/2015/08/27/epoch/).
 
+use std::cmp;
+use std::fmt;
+use std::hint::spin_loop;
+use std::iter::FromIterator;
+use std::mem::ManuallyDrop;
 use std::ptr;
+#[cfg(not(loom))]
 use std::sync::atomic::AtomicUsize;
+#[cfg(loom)]
+use loom::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::{Acquire, Release, AcqRel, Relaxed};
 
-use epoch::{self, Atomic, Owned};
+use epoch::{self, Atomic, Guard, Owned, Shared};
+
+/// Exponential backoff for retrying a failed CAS. Each call to `spin`
+/// busy-waits twice as long as the last, up to a limit, which keeps
+/// contending threads from hammering the same cache line.
+struct Backoff {
+    step: u32,
+}
+
+const MAX_BACKOFF_STEP: u32 = 6;
+
+impl Backoff {
+    fn new() -> Self {
+        Backoff { step: 0 }
+    }
+
+    fn spin(&mut self) {
+        for _ in 0 .. 1 << self.step {
+            spin_loop();
+        }
+
+        if self.step < MAX_BACKOFF_STEP {
+            self.step += 1;
+        }
+    }
+}
 
 /// A lock-free stack.
 ///
@@ -31,11 +64,24 @@ pub struct TreiberStack<T> {
     len:  AtomicUsize,
 }
 
+// The data is wrapped in `ManuallyDrop` because `pop` moves it out with
+// `ptr::read` before the node itself is reclaimed, so dropping a node
+// must not drop its data a second time. (`pop_cloned` instead drops the
+// data explicitly when it reclaims the node.)
 struct Node<T> {
-    data: T,
+    data: ManuallyDrop<T>,
     next: Atomic<Node<T>>,
 }
 
+impl<T> Node<T> {
+    fn new(data: T) -> Owned<Self> {
+        Owned::new(Node {
+            data: ManuallyDrop::new(data),
+            next: Atomic::null(),
+        })
+    }
+}
+
 impl<T> TreiberStack<T> {
     /// Returns a new, empty stack.
     pub fn new() -> TreiberStack<T> {
@@ -83,54 +129,351 @@ impl<T> TreiberStack<T> {
 
     /// Pushes an element on top of the stack.
     pub fn push(&self, data: T) {
-        let mut new_node = Owned::new(Node {
-            data: data,
-            next: Atomic::null(),
-        });
+        // Safe because the no-op `fix` never looks at the element below.
+        unsafe { self.push_with(data, |_, _| ()) }
+    }
+
+    /// Pushes an element on top of the stack, first calling `fix` with a
+    /// mutable reference to it and a reference to the element it will
+    /// sit on, if any. If another thread changes the top of the stack
+    /// before the push completes, `fix` is called again with the new top,
+    /// so the pushed element always reflects the one actually below it.
+    ///
+    /// # Safety
+    ///
+    /// `fix` reads the element below in place, so no other thread may
+    /// move an element out of the stack while this runs. That rules out
+    /// every kind of pop except `pop_cloned`, which leaves the original
+    /// in its node until the node is reclaimed.
+    pub(crate) unsafe fn push_with<F>(&self, data: T, mut fix: F)
+        where F: FnMut(&mut T, Option<&T>)
+    {
+        let mut new_node = Node::new(data);
 
         let guard = epoch::pin();
+        let mut backoff = Backoff::new();
 
         loop {
-            let head = self.head.load(Acquire, &guard);
-            new_node.next.store(head, Relaxed);
+            match self.try_push_node(new_node, &guard, &mut fix) {
+                Ok(()) => return,
+                Err(node) => new_node = node,
+            }
 
-            match self.head.compare_and_set(head, new_node, Release, &guard) {
-                Ok(_) => {
-                    self.len.fetch_add(1, AcqRel);
-                    return;
-                }
-                Err(owned) => new_node = owned.new,
+            backoff.spin();
+        }
+    }
+
+    /// Makes a single attempt to push an element, handing it back as
+    /// `Err` if another thread changed the head first. This lets a
+    /// wrapper decide what to do under contention instead of spinning.
+    pub(crate) fn try_push(&self, data: T) -> Result<(), T> {
+        // Safe because the no-op `fix` never looks at the element below.
+        unsafe { self.try_push_node(Node::new(data), &epoch::pin(), &mut |_, _| ()) }
+            .map_err(|node| ManuallyDrop::into_inner(node.into_box().data))
+    }
+
+    /// Makes a single attempt to push `new_node`. The safety condition is
+    /// the same as for `push_with`.
+    unsafe fn try_push_node<F>(&self, mut new_node: Owned<Node<T>>, guard: &Guard, fix: &mut F)
+                               -> Result<(), Owned<Node<T>>>
+        where F: FnMut(&mut T, Option<&T>)
+    {
+        // We dereference `head` only to show it to `fix`; the `Acquire`
+        // load makes its data visible, as in `try_pop_pinned`.
+        let head = self.head.load(Acquire, guard);
+        fix(&mut new_node.data, head.as_ref().map(|node| &*node.data));
+        // `Relaxed` suffices because the node isn't shared yet: the
+        // `Release` CAS below publishes this store along with the
+        // data.
+        new_node.next.store(head, Relaxed);
+
+        // On failure nothing was published, and we retry with a
+        // fresh load, so the failure ordering can be `Relaxed`.
+        match self.head.compare_exchange(head, new_node, Release, Relaxed, guard) {
+            Ok(_) => {
+                self.len.fetch_add(1, AcqRel);
+                Ok(())
             }
+            Err(err) => Err(err.new),
         }
     }
 
     /// Removes and returns the top element of the stack, or `None` if
     /// empty.
     pub fn pop(&self) -> Option<T> {
+        self.pop_pinned(&epoch::pin(), |_| true)
+    }
+
+    /// Removes up to `n` elements from the top of the stack, returning
+    /// them in the order they were popped. Returns fewer than `n`
+    /// elements if the stack runs out.
+    ///
+    /// This pins the epoch only once for the whole batch, which is
+    /// cheaper than calling `pop` in a loop.
+    pub fn pop_n(&self, n: usize) -> Vec<T> {
+        let guard = epoch::pin();
+        let mut result = Vec::with_capacity(cmp::min(n, self.len()));
+
+        while result.len() < n {
+            match self.pop_pinned(&guard, |_| true) {
+                Some(data) => result.push(data),
+                None => break,
+            }
+        }
+
+        result
+    }
+
+    fn pop_pinned<F>(&self, guard: &Guard, mut pred: F) -> Option<T>
+        where F: FnMut(&T) -> bool
+    {
+        let mut backoff = Backoff::new();
+
+        loop {
+            if let Ok(result) = self.try_pop_pinned(guard, &mut pred) {
+                return result;
+            }
+
+            backoff.spin();
+        }
+    }
+
+    /// Makes a single attempt to pop an element, returning `Err` if
+    /// another thread changed the head first. This lets a wrapper decide
+    /// what to do under contention instead of spinning.
+    pub(crate) fn try_pop(&self) -> Result<Option<T>, ()> {
+        self.try_pop_pinned(&epoch::pin(), &mut |_| true)
+    }
+
+    fn try_pop_pinned<F>(&self, guard: &Guard, pred: &mut F) -> Result<Option<T>, ()>
+        where F: FnMut(&T) -> bool
+    {
+        self.try_unlink(guard, pred).map(|unlinked| unlinked.map(|node| unsafe {
+            // Unlinking the node makes us the only thread that takes its
+            // data, and the `ManuallyDrop` keeps the collector from
+            // dropping it again.
+            guard.defer_destroy(node);
+            ManuallyDrop::into_inner(ptr::read(&node.deref().data))
+        }))
+    }
+
+    /// Removes the top element of the stack and returns a clone of it,
+    /// or `None` if empty.
+    ///
+    /// Unlike the other pops, this leaves the original element in its
+    /// node and drops it only when the node is reclaimed, after every
+    /// thread that might be reading it has unpinned. As long as a stack
+    /// is popped only this way, `push_with` and `peek_with` can safely
+    /// read elements in place. The deferred drop may run on another thread, after the
+    /// stack itself is gone, hence the `Send + 'static` bound.
+    pub(crate) fn pop_cloned(&self) -> Option<T>
+        where T: Clone + Send + 'static
+    {
         let guard = epoch::pin();
+        let mut backoff = Backoff::new();
 
         loop {
-            let shared_head = self.head.load(Acquire, &guard);
-            if let Some(head) = unsafe { shared_head.as_ref() } {
-                let next = head.next.load(Relaxed, &guard);
-
-                if self.head.compare_and_set(shared_head, next, Release, &guard).is_ok() {
-                    self.len.fetch_sub(1, AcqRel);
-                    return Some(unsafe {
-                        guard.defer(move || shared_head.into_owned());
-                        ptr::read(&head.data)
+            match self.try_unlink(&guard, &mut |_| true) {
+                Ok(Some(node)) => unsafe {
+                    // We're still pinned, so the node can't be freed until
+                    // after we're done cloning; scheduling it first means
+                    // that a panicking `clone` doesn't leak it.
+                    guard.defer_unchecked(move || {
+                        let mut node = node.into_owned();
+                        ManuallyDrop::drop(&mut node.data);
                     });
-                }
-            } else {
-                return None;
+                    return Some(T::clone(&node.deref().data));
+                },
+                Ok(None) => return None,
+                Err(()) => backoff.spin(),
+            }
+        }
+    }
+
+    /// Makes a single attempt to unlink the top node if its element
+    /// satisfies `pred`, returning `Err` if another thread changed the
+    /// head first. On success the caller owns the node's data and is
+    /// responsible for reclaiming the node.
+    fn try_unlink<'g, F>(&self, guard: &'g Guard, pred: &mut F)
+                         -> Result<Option<Shared<'g, Node<T>>>, ()>
+        where F: FnMut(&T) -> bool
+    {
+        // Memory orderings:
+        //
+        // Every write to `head` after construction is a CAS, that is, a
+        // read-modify-write, so they all extend the release sequence of
+        // the `Release` CAS that published any given node. Thus the
+        // `Acquire` load of `head` synchronizes with the push that
+        // published the node we load, even if pops and pushes have
+        // intervened since, and everything the pusher wrote before
+        // publishing — the data and the node's `next` — is visible.
+        //
+        // A node's `next` is written only before the node is published
+        // and never again, so loading it `Relaxed` reads that one value;
+        // it's already ordered by the `Acquire` load above and needs no
+        // ordering of its own. And because we are pinned, the node can't
+        // be reclaimed and its address reused while we hold it, so if our
+        // CAS succeeds then `next` is still the correct successor (no ABA).
+        //
+        // The successful CAS doesn't need `Release` for the next popper:
+        // as above, it extends the release sequence whatever its ordering.
+        // Nor has the popper written anything before it that another
+        // thread must see, since the node is reclaimed through the epoch
+        // collector, which does its own synchronization. So `Relaxed`
+        // would do (the loom test passes with it), and `Release` is kept
+        // only to be conservative. We need no `Acquire` on it either:
+        // we've already acquired the node through the load of `head`. On
+        // failure we retry with a fresh `Acquire` load, so `Relaxed`
+        // suffices.
+        let shared_head = self.head.load(Acquire, guard);
+        let head = match unsafe { shared_head.as_ref() } {
+            Some(head) => head,
+            None => return Ok(None),
+        };
+
+        if !pred(&head.data) {
+            return Ok(None);
+        }
+
+        let next = head.next.load(Relaxed, guard);
+
+        match self.head.compare_exchange(shared_head, next, Release, Relaxed, guard) {
+            Ok(_) => {
+                self.len.fetch_sub(1, AcqRel);
+                Ok(Some(shared_head))
             }
+            Err(_) => Err(()),
         }
     }
+
+    /// Pops elements until the stack is empty.
+    ///
+    /// This is best-effort: it drains the current contents, but
+    /// elements pushed concurrently may or may not be removed.
+    pub fn clear(&self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Moves all the elements of `other` onto `self`, leaving `other`
+    /// empty.
+    ///
+    /// Elements are popped from `other` and pushed onto `self` one at a
+    /// time, which reverses their order: the bottom of `other` ends up
+    /// on top of `self`. Appending a stack to itself does nothing.
+    pub fn append(&self, other: &TreiberStack<T>) {
+        if ptr::eq(self, other) {
+            return;
+        }
+
+        while let Some(data) = other.pop() {
+            self.push(data);
+        }
+    }
+
+    /// Removes the elements that don't satisfy `pred`, keeping the rest
+    /// in their original order.
+    ///
+    /// This works by popping everything and pushing the survivors back,
+    /// so it must not run concurrently with other pushes or pops: their
+    /// elements could end up interleaved with the survivors, or a pop
+    /// could see the stack empty in the middle.
+    pub fn retain<F: Fn(&T) -> bool>(&self, pred: F) {
+        let mut kept = Vec::with_capacity(self.len());
+
+        while let Some(data) = self.pop() {
+            if pred(&data) {
+                kept.push(data);
+            }
+        }
+
+        for data in kept.into_iter().rev() {
+            self.push(data);
+        }
+    }
+
+    /// Returns an iterator that pops one element each time it's advanced,
+    /// stopping when the stack is empty. Unlike `into_vec`, this can stop
+    /// part way, leaving the rest of the elements on the stack.
+    ///
+    /// The iterator only borrows the stack, so other threads may push
+    /// and pop while it's in use; their pushes may be drained too.
+    pub fn drain(&self) -> Drain<'_, T> {
+        Drain(self)
+    }
+
+    /// Converts the stack into a vector of its elements in LIFO order,
+    /// so the top of the stack comes first.
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Iterates over the elements from the top down, as of the moment
+    /// each node is reached. The guard keeps the nodes from being
+    /// reclaimed while they're borrowed, but not the elements in them,
+    /// so the elements may be read only while nothing pops.
+    fn snapshot<'g>(&self, guard: &'g Guard) -> Snapshot<'g, T> {
+        Snapshot {
+            current: self.head.load(Acquire, guard),
+            guard,
+        }
+    }
+
+    /// Calls `f` on a reference to the top element of the stack, if
+    /// there is one, returning its result. Unlike `peek`, this doesn't
+    /// require `T: Clone`.
+    ///
+    /// # Safety
+    ///
+    /// No other thread may pop from the stack while this runs. Pinning
+    /// the epoch keeps the top node's memory alive, but not the element
+    /// in it: a concurrent pop moves the element out and hands it to its
+    /// caller, who may drop it while `f` is still looking at it. `peek`
+    /// has the same race; see the caveat there.
+    pub unsafe fn peek_with<R, F: FnOnce(&T) -> R>(&self, f: F) -> Option<R> {
+        let guard = epoch::pin();
+        let shared_head = self.head.load(Acquire, &guard);
+        shared_head.as_ref().map(|head| f(&head.data))
+    }
+
+    /// Counts the nodes in the stack by walking the chain, as a check on
+    /// the `len` counter. Meant for tests and debugging.
+    ///
+    /// This takes O(n) time, and if other threads are pushing or popping
+    /// the count may match no state the stack was ever actually in.
+    pub fn count_nodes(&self) -> usize {
+        self.snapshot(&epoch::pin()).count()
+    }
+}
+
+impl<T: Copy> TreiberStack<T> {
+    /// Removes and returns the top element of the stack if it satisfies
+    /// `pred`, or returns `None` if the stack is empty or the top element
+    /// doesn't satisfy `pred`.
+    ///
+    /// If another thread pushes or pops between checking the predicate
+    /// and removing the element, the predicate is checked again against
+    /// the new top, so the element removed always satisfies it.
+    ///
+    /// `pred` looks at the top element in place, which is why this needs
+    /// `T: Copy`. Pinning keeps the node's memory alive, and a concurrent
+    /// pop only copies a `Copy` element out, never writing or dropping
+    /// it, so the element stays intact for as long as `pred` reads it.
+    pub fn pop_if<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<T> {
+        self.pop_pinned(&epoch::pin(), pred)
+    }
 }
 
 impl<T: Clone> TreiberStack<T> {
     /// Gets a clone of the top element of the stack, if there is one.
     ///
+    /// # Caveat
+    ///
+    /// Like `peek_with`, this clones the element in place, so a pop on
+    /// another thread can move the element out and drop it while the
+    /// clone is still reading it. `peek` was safe before `peek_with`
+    /// existed and is left safe for its callers, but it has the same
+    /// contract in practice: don't call it while other threads may pop.
+    ///
     /// # Example
     ///
     /// ```
@@ -144,18 +487,77 @@ impl<T: Clone> TreiberStack<T> {
     /// assert_eq!(Some(4), stack.peek());
     /// ```
     pub fn peek(&self) -> Option<T> {
+        // FIXME: this should be `unsafe` like `peek_with`, or restricted
+        // to stacks popped only with `pop_cloned`; changing it breaks the
+        // public API, so it's deferred.
         let guard = epoch::pin();
         let shared_head = self.head.load(Acquire, &guard);
-        unsafe { shared_head.as_ref() }.map(|head| head.data.clone())
+        unsafe { shared_head.as_ref() }.map(|head| T::clone(&head.data))
+    }
+
+    /// Returns a vector of clones of the elements, top first, without
+    /// modifying the stack.
+    ///
+    /// This reads the elements in place, which would race with a pop
+    /// freeing them, so it needs exclusive access to the stack.
+    pub fn to_vec(&mut self) -> Vec<T> {
+        self.snapshot(&epoch::pin()).cloned().collect()
+    }
+}
+
+impl<T: PartialEq> TreiberStack<T> {
+    /// Checks whether the stack contains an element equal to `value`.
+    ///
+    /// Like `to_vec`, this compares the elements in place, so it needs
+    /// exclusive access to the stack. It's meant for tests.
+    pub fn contains(&mut self, value: &T) -> bool {
+        self.snapshot(&epoch::pin()).any(|data| data == value)
+    }
+
+    /// Compares two stacks element by element, from the top down. This
+    /// takes the place of `PartialEq`, which can't require exclusive
+    /// access: like `contains`, it compares the elements in place.
+    pub fn eq_exclusive(&mut self, other: &mut Self) -> bool {
+        let guard = epoch::pin();
+        self.snapshot(&guard).eq(other.snapshot(&guard))
+    }
+}
+
+struct Snapshot<'g, T: 'g> {
+    current: Shared<'g, Node<T>>,
+    guard:   &'g Guard,
+}
+
+impl<'g, T> Iterator for Snapshot<'g, T> {
+    type Item = &'g T;
+
+    fn next(&mut self) -> Option<&'g T> {
+        unsafe { self.current.as_ref() }.map(|node| {
+            self.current = node.next.load(Acquire, self.guard);
+            &*node.data
+        })
     }
 }
 
 impl<T> Drop for TreiberStack<T> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        // Having `&mut self` means no other thread can be looking at the
+        // stack, so we can free the nodes immediately rather than
+        // deferring their reclamation to the epoch collector.
+        unsafe {
+            let guard = epoch::unprotected();
+            let mut current = self.head.load(Relaxed, guard);
+
+            while !current.is_null() {
+                let mut node = current.into_owned();
+                ManuallyDrop::drop(&mut node.data);
+                current = node.next.load(Relaxed, guard);
+            }
+        }
     }
 }
 
+#[cfg(not(loom))]
 #[test]
 fn two_threads_cooperate() {
     use std::{sync, thread};
@@ -189,3 +591,542 @@ fn two_threads_cooperate() {
 
     assert_eq!(expected, actual);
 }
+
+#[cfg(all(test, not(loom)))]
+struct DropCounter<'a>(&'a AtomicUsize);
+
+#[cfg(all(test, not(loom)))]
+impl<'a> Drop for DropCounter<'a> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Relaxed);
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn drop_frees_remaining_elements() {
+    let drops = AtomicUsize::new(0);
+
+    {
+        let stack = TreiberStack::new();
+        for _ in 0 .. 5 {
+            stack.push(DropCounter(&drops));
+        }
+
+        drop(stack.pop());
+        drop(stack.pop());
+        assert_eq!(2, drops.load(Relaxed));
+    }
+
+    assert_eq!(5, drops.load(Relaxed));
+}
+
+/// A consuming iterator over the elements of a `TreiberStack`, in LIFO
+/// order.
+///
+/// Created by the `into_iter` method of `TreiberStack`.
+pub struct IntoIter<T>(TreiberStack<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> IntoIterator for TreiberStack<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+/// An iterator that lazily pops elements from a borrowed stack.
+///
+/// Created by the `drain` method of `TreiberStack`.
+pub struct Drain<'a, T: 'a>(&'a TreiberStack<T>);
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn drain_pops_lazily() {
+    let stack: TreiberStack<_> = (0 .. 5).collect();
+
+    let drained: Vec<_> = stack.drain().take(3).collect();
+    assert_eq!(vec![4, 3, 2], drained);
+    assert_eq!(2, stack.len());
+    assert_eq!(vec![1, 0], stack.drain().collect::<Vec<_>>());
+    assert!(stack.is_empty());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn into_iter_pops_in_lifo_order() {
+    let stack = TreiberStack::new();
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+
+    let actual: Vec<_> = stack.into_iter().collect();
+    assert_eq!(vec![3, 2, 1], actual);
+}
+
+impl<T> FromIterator<T> for TreiberStack<T> {
+    /// Builds a stack by pushing each element in order, so the last
+    /// element of the iterator ends up on top.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let stack = TreiberStack::new();
+
+        for data in iter {
+            stack.push(data);
+        }
+
+        stack
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn collect_pushes_in_order() {
+    let stack: TreiberStack<i32> = (0 .. 5).collect();
+
+    assert_eq!(5, stack.len());
+    for i in (0 .. 5).rev() {
+        assert_eq!(Some(i), stack.pop());
+    }
+    assert_eq!(None, stack.pop());
+}
+
+impl<T> Extend<T> for TreiberStack<T> {
+    /// Pushes each element in order.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push(data);
+        }
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn extend_pushes_each_element() {
+    let mut stack: TreiberStack<i32> = (0 .. 2).collect();
+    stack.extend(vec![2, 3, 4]);
+    stack.extend([5, 6].iter().cloned());
+
+    assert_eq!(7, stack.len());
+    let actual: Vec<_> = stack.into_iter().collect();
+    assert_eq!(vec![6, 5, 4, 3, 2, 1, 0], actual);
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        TreiberStack::new()
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn default_is_empty() {
+    #[derive(Default)]
+    struct Queues {
+        work: TreiberStack<usize>,
+    }
+
+    let stack: TreiberStack<i32> = Default::default();
+    assert!(stack.is_empty());
+    assert!(Queues::default().work.is_empty());
+}
+
+impl<T> fmt::Debug for TreiberStack<T> {
+    /// Shows only the length, since reading the elements would mean
+    /// traversing nodes that other threads may be popping.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TreiberStack")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(not(loom))]
+#[test]
+fn debug_shows_len() {
+    let stack: TreiberStack<i32> = (0 .. 3).collect();
+    assert_eq!("TreiberStack { len: 3 }", format!("{:?}", stack));
+}
+
+#[cfg(not(loom))]
+#[test]
+fn eq_exclusive_compares_elements_top_down() {
+    let mut a: TreiberStack<_> = (0 .. 3).collect();
+    let mut b: TreiberStack<_> = (0 .. 3).collect();
+    let mut c: TreiberStack<_> = (0 .. 3).rev().collect();
+    let mut d: TreiberStack<_> = (0 .. 2).collect();
+
+    assert!(a.eq_exclusive(&mut b));
+    assert!(! a.eq_exclusive(&mut c));
+    assert!(! a.eq_exclusive(&mut d));
+    assert!(! d.eq_exclusive(&mut a));
+    assert!(TreiberStack::<i32>::new().eq_exclusive(&mut TreiberStack::new()));
+
+    assert_eq!(vec![2, 1, 0], a.into_vec());
+    assert_eq!(vec![0, 1, 2], c.into_vec());
+    assert_eq!(vec![1, 0], d.into_vec());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn peek_with_borrows_top() {
+    let stack = TreiberStack::new();
+    assert_eq!(None, unsafe { stack.peek_with(|s: &String| s.len()) });
+
+    stack.push("hello".to_owned());
+    stack.push("lock-free".to_owned());
+    assert_eq!(Some(9), unsafe { stack.peek_with(|s| s.len()) });
+    assert_eq!(2, stack.len());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn many_threads_push_and_pop() {
+    use std::{sync, thread};
+
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 1000;
+
+    let stack = sync::Arc::new(TreiberStack::new());
+
+    let handles: Vec<_> = (0 .. THREADS).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            let mut popped = Vec::new();
+            for i in 0 .. PER_THREAD {
+                stack.push(t * PER_THREAD + i);
+                if i % 2 == 0 {
+                    popped.extend(stack.pop());
+                }
+            }
+            popped
+        })
+    }).collect();
+
+    let mut actual: Vec<usize> = handles.into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    assert_eq!(THREADS * PER_THREAD / 2, actual.len());
+    assert_eq!(THREADS * PER_THREAD / 2, stack.len());
+
+    while let Some(element) = stack.pop() {
+        actual.push(element);
+    }
+    actual.sort();
+
+    let expected: Vec<usize> = (0 .. THREADS * PER_THREAD).collect();
+    assert_eq!(expected, actual);
+}
+
+#[cfg(not(loom))]
+#[test]
+fn clear_empties_the_stack() {
+    let drops = AtomicUsize::new(0);
+    let stack = TreiberStack::new();
+    for _ in 0 .. 4 {
+        stack.push(DropCounter(&drops));
+    }
+
+    stack.clear();
+    assert!(stack.is_empty());
+    assert_eq!(0, stack.len());
+    assert_eq!(4, drops.load(Relaxed));
+
+    stack.push(DropCounter(&drops));
+    assert_eq!(1, stack.len());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn to_vec_and_into_vec() {
+    let mut stack: TreiberStack<i32> = (1 .. 4).collect();
+
+    let snapshot = stack.to_vec();
+    assert_eq!(vec![3, 2, 1], snapshot);
+    assert_eq!(3, stack.len());
+
+    stack.push(4);
+    assert_eq!(vec![3, 2, 1], snapshot);
+    assert_eq!(vec![4, 3, 2, 1], stack.to_vec());
+
+    assert_eq!(vec![4, 3, 2, 1], stack.into_vec());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn contains_checks_membership() {
+    let mut stack = TreiberStack::new();
+    assert!(! stack.contains(&"a".to_owned()));
+
+    stack.push("a".to_owned());
+    stack.push("b".to_owned());
+    stack.push("c".to_owned());
+
+    assert!(stack.contains(&"a".to_owned()));
+    assert!(stack.contains(&"c".to_owned()));
+    assert!(! stack.contains(&"d".to_owned()));
+
+    stack.pop();
+    assert!(! stack.contains(&"c".to_owned()));
+}
+
+#[cfg(not(loom))]
+#[test]
+fn pop_n_takes_up_to_n() {
+    let stack: TreiberStack<i32> = (0 .. 5).collect();
+
+    assert_eq!(vec![4, 3], stack.pop_n(2));
+    assert_eq!(Vec::<i32>::new(), stack.pop_n(0));
+    assert_eq!(vec![2, 1, 0], stack.pop_n(10));
+    assert!(stack.is_empty());
+    assert_eq!(Vec::<i32>::new(), stack.pop_n(1));
+}
+
+#[cfg(not(loom))]
+#[test]
+fn pop_if_checks_the_top() {
+    let stack: TreiberStack<i32> = vec![1, 2, 3].into_iter().collect();
+
+    assert_eq!(None, stack.pop_if(|&n| n < 3));
+    assert_eq!(3, stack.len());
+
+    assert_eq!(Some(3), stack.pop());
+    assert_eq!(Some(2), stack.pop_if(|&n| n < 3));
+    assert_eq!(Some(1), stack.pop_if(|&n| n < 3));
+    assert_eq!(None, stack.pop_if(|_| true));
+}
+
+#[cfg(not(loom))]
+#[test]
+fn pop_if_rechecks_after_contention() {
+    use std::{sync, thread};
+
+    const PER_THREAD: usize = 1000;
+
+    let stack = sync::Arc::new(TreiberStack::new());
+
+    // Every thread pushes, pops, and pops conditionally, so the top
+    // keeps changing under each `pop_if`.
+    let handles: Vec<_> = (0 .. 4).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            let mut popped = Vec::new();
+            let mut popped_if = Vec::new();
+            for i in 0 .. PER_THREAD {
+                stack.push(t * PER_THREAD + i);
+                if i % 3 == 0 {
+                    popped.extend(stack.pop());
+                }
+                popped_if.extend(stack.pop_if(|&n| n % 2 == 0));
+            }
+            (popped, popped_if)
+        })
+    }).collect();
+
+    let mut actual = Vec::new();
+    for handle in handles {
+        let (popped, popped_if) = handle.join().unwrap();
+        for &n in &popped_if {
+            assert_eq!(0, n % 2);
+        }
+        actual.extend(popped);
+        actual.extend(popped_if);
+    }
+
+    while let Some(n) = stack.pop() {
+        actual.push(n);
+    }
+    actual.sort();
+
+    let expected: Vec<usize> = (0 .. 4 * PER_THREAD).collect();
+    assert_eq!(expected, actual);
+}
+
+#[cfg(not(loom))]
+#[test]
+fn producers_and_consumers_stress() {
+    use std::{sync, thread};
+    use std::sync::atomic::AtomicBool;
+
+    const PRODUCERS: usize = 4;
+    const CONSUMERS: usize = 4;
+    const PER_PRODUCER: usize = 10_000;
+
+    let stack = sync::Arc::new(TreiberStack::new());
+    let done = sync::Arc::new(AtomicBool::new(false));
+
+    let producers: Vec<_> = (0 .. PRODUCERS).map(|p| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            for i in 0 .. PER_PRODUCER {
+                stack.push(Box::new(p * PER_PRODUCER + i));
+            }
+        })
+    }).collect();
+
+    let consumers: Vec<_> = (0 .. CONSUMERS).map(|_| {
+        let stack = stack.clone();
+        let done = done.clone();
+        thread::spawn(move || {
+            let mut popped = Vec::new();
+            loop {
+                match stack.pop() {
+                    Some(element) => popped.push(*element),
+                    None if done.load(Acquire) => break,
+                    None => spin_loop(),
+                }
+            }
+            popped
+        })
+    }).collect();
+
+    for handle in producers {
+        handle.join().unwrap();
+    }
+    done.store(true, Release);
+
+    let mut actual: Vec<usize> = consumers.into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    actual.sort();
+
+    let expected: Vec<usize> = (0 .. PRODUCERS * PER_PRODUCER).collect();
+    assert_eq!(expected, actual);
+    assert!(stack.is_empty());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn high_contention_reads_whole_nodes() {
+    use std::{sync, thread};
+
+    const THREADS: usize = 8;
+    const ROUNDS: usize = 20_000;
+
+    // Each element carries a checksum, so reading a node through a stale
+    // or unsynchronized pointer would show up as a torn pair.
+    let stack = sync::Arc::new(TreiberStack::new());
+
+    let handles: Vec<_> = (0 .. THREADS).map(|t| {
+        let stack = stack.clone();
+        thread::spawn(move || {
+            let mut popped = 0;
+            for i in 0 .. ROUNDS {
+                let n = t * ROUNDS + i;
+                stack.push(Box::new((n, !n)));
+                if let Some(pair) = stack.pop() {
+                    assert_eq!(pair.0, !pair.1);
+                    popped += 1;
+                }
+            }
+            popped
+        })
+    }).collect();
+
+    let popped: usize = handles.into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum();
+    assert_eq!(THREADS * ROUNDS, popped + stack.len());
+}
+
+#[cfg(loom)]
+#[test]
+fn loom_push_and_pop_lose_nothing() {
+    use loom::{sync, thread};
+
+    // Loom also explores crossbeam-epoch's own bookkeeping, so an
+    // unbounded search takes hours. Three preemptions still cover every
+    // way the two CASes on `head` can interleave. Set
+    // `LOOM_MAX_PREEMPTIONS` to override.
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound.get_or_insert(3);
+
+    builder.check(|| {
+        let stack = sync::Arc::new(TreiberStack::new());
+
+        let other = stack.clone();
+        let handle = thread::spawn(move || {
+            other.push(1);
+            other.pop()
+        });
+
+        stack.push(2);
+        let mine = stack.pop();
+        let theirs = handle.join().unwrap();
+
+        // Each thread pushes before it pops, so both pops must succeed.
+        let mut actual = vec![mine.unwrap(), theirs.unwrap()];
+        actual.sort();
+        assert_eq!(vec![1, 2], actual);
+        assert_eq!(0, stack.len());
+        assert!(stack.is_empty());
+    });
+}
+
+#[cfg(not(loom))]
+#[test]
+fn count_nodes_agrees_with_len() {
+    let stack = TreiberStack::new();
+    assert_eq!(0, stack.count_nodes());
+
+    for i in 0 .. 10 {
+        stack.push(i);
+        assert_eq!(stack.len(), stack.count_nodes());
+    }
+
+    stack.pop();
+    stack.pop_if(|&n| n == 8);
+    stack.pop_n(3);
+    assert_eq!(5, stack.count_nodes());
+    assert_eq!(stack.len(), stack.count_nodes());
+
+    stack.clear();
+    assert_eq!(0, stack.count_nodes());
+    assert_eq!(stack.len(), stack.count_nodes());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn retain_keeps_matching_elements_in_order() {
+    let stack: TreiberStack<_> = (0 .. 10).collect();
+
+    stack.retain(|&n| n % 2 == 1);
+
+    assert_eq!(5, stack.len());
+    assert_eq!(vec![9, 7, 5, 3, 1], stack.into_vec());
+}
+
+#[cfg(not(loom))]
+#[test]
+fn append_reverses_other_onto_self() {
+    let stack: TreiberStack<_> = vec![1, 2].into_iter().collect();
+    let other: TreiberStack<_> = vec![3, 4].into_iter().collect();
+
+    stack.append(&other);
+    assert_eq!(4, stack.len());
+    assert!(other.is_empty());
+
+    stack.append(&stack);
+    assert_eq!(vec![3, 4, 2, 1], stack.into_vec());
+}